@@ -1,9 +1,10 @@
-use proc_macro2::Span;
-use syn::parse::{Error, Result};
+use proc_macro2::{Delimiter, Span, TokenStream as TokenStream2, TokenTree};
+use quote::{quote, ToTokens};
+use syn::parse::{Error, Parse, ParseStream, Result};
 use syn::spanned::Spanned;
-use syn::{Attribute, Ident, Lit, LitStr, Meta, NestedMeta, Path};
+use syn::{bracketed, parenthesized, token, Attribute, Ident, Lit, LitStr, Meta, NestedMeta, Path, Token};
 
-use crate::structures::{Checks, Colour, HelpBehaviour, OnlyIn, Permissions};
+use crate::structures::{ArgsBound, Checks, CollapsedText, Colour, DescriptionPart, Example, GroupOrder, HelpBehaviour, HelpText, Metrics, OnlyIn, Permissions, Preset, StaticCase};
 use crate::util::{AsOption, LitExt};
 
 use std::fmt::{self, Write};
@@ -59,24 +60,497 @@ fn to_ident(p: Path) -> Result<Ident> {
     Ok(p.segments[0].ident.clone())
 }
 
+/// Parses the `#[description(include = "path")]` and `#[description(env = "VAR")]` forms.
+pub fn parse_description_source(attr: &Attribute) -> Result<Option<DescriptionPart>> {
+    let list = match attr.parse_meta() {
+        Ok(Meta::List(list)) if list.nested.len() == 1 => list,
+        _ => return Ok(None),
+    };
+
+    let nv = match &list.nested[0] {
+        NestedMeta::Meta(Meta::NameValue(nv)) => nv,
+        _ => return Ok(None),
+    };
+
+    let (part, expected): (fn(LitStr) -> DescriptionPart, _) = if nv.path.is_ident("include") {
+        (DescriptionPart::Include, "`include` expects a string literal path")
+    } else if nv.path.is_ident("env") {
+        (DescriptionPart::Env, "`env` expects a string literal naming an environment variable")
+    } else {
+        return Ok(None);
+    };
+
+    match &nv.lit {
+        Lit::Str(s) => Ok(Some(part(s.clone()))),
+        lit => Err(Error::new(lit.span(), expected)),
+    }
+}
+
+/// Parses `#[order(n)]` or `#[order = n]`, where `n` is a possibly negative integer.
+///
+/// A negative integer is not a literal, so it cannot go through [`parse_values`].
+///
+/// [`parse_values`]: fn.parse_values.html
+pub fn parse_signed(attr: &Attribute) -> Result<i32> {
+    fn signed(input: ParseStream<'_>) -> Result<i32> {
+        let negative = input.parse::<Option<Token![-]>>()?.is_some();
+        let lit = input.parse::<syn::LitInt>()?;
+
+        let digits = if negative {
+            format!("-{}", lit.base10_digits())
+        } else {
+            lit.base10_digits().to_string()
+        };
+
+        digits
+            .parse()
+            .map_err(|_| Error::new(lit.span(), "expected a 32-bit, signed integer"))
+    }
+
+    let parser = |input: ParseStream<'_>| {
+        if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+
+            return signed(input);
+        }
+
+        let content;
+        parenthesized!(content in input);
+
+        signed(&content)
+    };
+
+    syn::parse::Parser::parse2(parser, attr.tokens.clone())
+}
+
+/// Parses the keyed forms of `#[example]`, `#[example(input = "...", output = "...")]` and
+/// `#[example("...", requires = PERMISSION)]`. Either may mark the example as only relevant
+/// to users with some permissions, given as `requires = PERMISSION | PERMISSION | ...`.
+///
+/// Returns `None` if the attribute is not in one of these forms.
+pub fn parse_keyed_example(attr: &Attribute) -> Result<Option<Example>> {
+    let tokens = match attr.tokens.clone().into_iter().next() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => {
+            group.stream().into_iter().collect::<Vec<_>>()
+        },
+        _ => return Ok(None),
+    };
+
+    let keyed = tokens.windows(2).any(|pair| match pair {
+        [TokenTree::Ident(_), TokenTree::Punct(p)] => p.as_char() == '=',
+        _ => false,
+    });
+
+    if !keyed {
+        return Ok(None);
+    }
+
+    attr.parse_args_with(|input: ParseStream<'_>| {
+        let mut example_input = None;
+        let mut output = None;
+        let mut requires = None;
+
+        // The input may be given positionally, ahead of the keys.
+        if input.peek(LitStr) {
+            example_input = Some(input.parse::<LitStr>()?.value());
+
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        while !input.is_empty() {
+            let key = input.parse::<Ident>().map_err(|e| {
+                Error::new(e.span(), "expected `input = \"...\"`, `output = \"...\"` or `requires = ...`")
+            })?;
+            input.parse::<Token![=]>()?;
+
+            if key == "requires" {
+                if requires.is_some() {
+                    return Err(Error::new(key.span(), "specified more than once"));
+                }
+
+                let mut permissions = Permissions::default();
+
+                loop {
+                    let permission = input.parse::<Ident>()?;
+
+                    match Permissions::from_str(&permission.to_string()) {
+                        Some(p) => permissions.0 |= p.0,
+                        None => return Err(Error::new(permission.span(), "invalid permission")),
+                    }
+
+                    if input.parse::<Option<Token![|]>>()?.is_none() {
+                        break;
+                    }
+                }
+
+                requires = Some(permissions);
+            } else {
+                let slot = if key == "input" {
+                    &mut example_input
+                } else if key == "output" {
+                    &mut output
+                } else {
+                    return Err(Error::new(key.span(), "expected `input`, `output` or `requires`"));
+                };
+
+                if slot.is_some() {
+                    return Err(Error::new(key.span(), "specified more than once"));
+                }
+
+                match input.parse::<Lit>()? {
+                    Lit::Str(s) => *slot = Some(s.value()),
+                    lit => return Err(Error::new(lit.span(), "expected a string")),
+                }
+            }
+
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        match example_input {
+            Some(example_input) => Ok(Some(Example {
+                input: example_input,
+                output: output.unwrap_or_default(),
+                requires: requires.unwrap_or_default(),
+            })),
+            None => Err(Error::new(attr.span(), "a keyed example requires an `input`")),
+        }
+    })
+}
+
+/// Parses the keyed form of `#[help_available]`, `#[help_available(false, reason = "...")]`,
+/// into the reason the command is hidden from the help.
+///
+/// Returns `None` if the attribute is not in this form.
+pub fn parse_help_unavailable_reason(attr: &Attribute) -> Result<Option<String>> {
+    let list = match attr.parse_meta() {
+        Ok(Meta::List(list)) => list,
+        _ => return Ok(None),
+    };
+
+    let reason = match list.nested.iter().find_map(|nested| match nested {
+        NestedMeta::Meta(Meta::NameValue(nv)) => Some(nv),
+        _ => None,
+    }) {
+        Some(reason) => reason,
+        None => return Ok(None),
+    };
+
+    let mut nested = list.nested.iter();
+
+    match nested.next() {
+        Some(NestedMeta::Lit(Lit::Bool(b))) if !b.value => {}
+        _ => {
+            return Err(Error::new(
+                attr.span(),
+                "a reason is only given to hidden commands, as in `#[help_available(false, reason = \"...\")]`",
+            ))
+        }
+    }
+
+    if !reason.path.is_ident("reason") {
+        return Err(Error::new(reason.path.span(), "expected `reason`"));
+    }
+
+    if let Some(extra) = nested.nth(1) {
+        return Err(Error::new(extra.span(), "expected only `false` and a `reason`"));
+    }
+
+    match &reason.lit {
+        Lit::Str(s) if !s.value().is_empty() => Ok(Some(s.value())),
+        lit => Err(Error::new(lit.span(), "expected a non-empty string")),
+    }
+}
+
+/// Parses a list of translations, such as `#[localized_names(locale = "name", ...)]`,
+/// into pairs of a locale and a text. `what` names the translated text in errors.
+pub fn parse_localized(attr: &Attribute, what: &str) -> Result<Vec<(String, String)>> {
+    let list = match attr.parse_meta()? {
+        Meta::List(list) if !list.nested.is_empty() => list,
+        meta => {
+            return Err(Error::new(
+                attr.span(),
+                format_args!(
+                    "expected `#[{}(locale = \"{}\", ...)]`",
+                    meta.path().get_ident().map(|i| i.to_string()).unwrap_or_default(),
+                    what
+                ),
+            ))
+        }
+    };
+
+    let mut names: Vec<(String, String)> = Vec::new();
+
+    for nested in &list.nested {
+        let nv = match nested {
+            NestedMeta::Meta(Meta::NameValue(nv)) => nv,
+            _ => {
+                return Err(Error::new(
+                    nested.span(),
+                    format_args!("expected `locale = \"{}\"`", what),
+                ))
+            }
+        };
+
+        let locale = nv.path.get_ident().map(|i| i.to_string()).unwrap_or_default();
+
+        if locale.is_empty() || !locale.chars().all(|c| c.is_ascii_lowercase() || c == '_') {
+            return Err(Error::new(nv.path.span(), "locales must be lowercase ASCII, such as `en` or `pt_br`"));
+        }
+
+        if names.iter().any(|(l, _)| *l == locale) {
+            return Err(Error::new(
+                nv.path.span(),
+                format_args!("the locale `{}` is specified more than once", locale),
+            ));
+        }
+
+        let name = match &nv.lit {
+            Lit::Str(s) if !s.value().is_empty() => s.value(),
+            lit => return Err(Error::new(lit.span(), "expected a non-empty string")),
+        };
+
+        names.push((locale, name));
+    }
+
+    Ok(names)
+}
+
+/// Parses `#[metrics(name = "label", tags = ["tag", ...])]`. The `tags` are optional.
+pub fn parse_metrics(attr: &Attribute) -> Result<Metrics> {
+    fn label(input: ParseStream<'_>) -> Result<String> {
+        let s = input.parse::<LitStr>()?;
+
+        if s.value().is_empty() {
+            return Err(Error::new(s.span(), "expected a non-empty string"));
+        }
+
+        Ok(s.value())
+    }
+
+    let parser = |input: ParseStream<'_>| {
+        let content;
+        parenthesized!(content in input);
+
+        let mut name = None;
+        let mut tags: Option<Vec<String>> = None;
+
+        while !content.is_empty() {
+            let key = content.parse::<Ident>()?;
+            content.parse::<Token![=]>()?;
+
+            if key == "name" {
+                if name.is_some() {
+                    return Err(Error::new(key.span(), "`name` is specified more than once"));
+                }
+
+                name = Some(label(&content)?);
+            } else if key == "tags" {
+                if tags.is_some() {
+                    return Err(Error::new(key.span(), "`tags` is specified more than once"));
+                }
+
+                let list;
+                bracketed!(list in content);
+
+                let mut values = Vec::new();
+
+                while !list.is_empty() {
+                    let tag = label(&list)?;
+
+                    if !values.contains(&tag) {
+                        values.push(tag);
+                    }
+
+                    if !list.is_empty() {
+                        list.parse::<Token![,]>()?;
+                    }
+                }
+
+                tags = Some(values);
+            } else {
+                return Err(Error::new(key.span(), "expected `name` or `tags`"));
+            }
+
+            if !content.is_empty() {
+                content.parse::<Token![,]>()?;
+            }
+        }
+
+        match name {
+            Some(name) => Ok(Metrics {
+                name,
+                tags: tags.unwrap_or_default(),
+            }),
+            None => Err(Error::new(attr.span(), "`metrics` requires a `name`")),
+        }
+    };
+
+    syn::parse::Parser::parse2(parser, attr.tokens.clone())
+}
+
+/// The kind of a value given to an option, as it was written.
+///
+/// Identifiers are turned into string literals when parsed, so their kind is
+/// the only way to tell them apart.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LitKind {
+    Str,
+    ByteStr,
+    Byte,
+    Char,
+    Int,
+    Float,
+    Bool,
+    Ident,
+    Verbatim,
+}
+
+impl LitKind {
+    /// The kinds that may stand for text.
+    const TEXT: &'static [LitKind] = &[LitKind::Str, LitKind::ByteStr, LitKind::Byte, LitKind::Char, LitKind::Ident];
+
+    /// The kinds that may name an item.
+    const NAME: &'static [LitKind] = &[LitKind::Str, LitKind::Ident];
+
+    /// The kinds that may stand for a number, either written as is or in a string.
+    const NUMBER: &'static [LitKind] = &[LitKind::Int, LitKind::Str];
+
+    pub fn of(lit: &Lit) -> Self {
+        match lit {
+            Lit::Str(_) => LitKind::Str,
+            Lit::ByteStr(_) => LitKind::ByteStr,
+            Lit::Byte(_) => LitKind::Byte,
+            Lit::Char(_) => LitKind::Char,
+            Lit::Int(_) => LitKind::Int,
+            Lit::Float(_) => LitKind::Float,
+            Lit::Bool(_) => LitKind::Bool,
+            Lit::Verbatim(_) => LitKind::Verbatim,
+        }
+    }
+}
+
+impl fmt::Display for LitKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            LitKind::Str => "string literal",
+            LitKind::ByteStr => "byte string literal",
+            LitKind::Byte => "byte literal",
+            LitKind::Char => "character literal",
+            LitKind::Int => "integer literal",
+            LitKind::Float => "float literal",
+            LitKind::Bool => "boolean literal",
+            LitKind::Ident => "identifier",
+            LitKind::Verbatim => "literal",
+        })
+    }
+}
+
 #[derive(Debug)]
 pub struct Values {
     pub name: Ident,
     pub literals: Vec<Lit>,
+    /// The kind of each of the `literals`, as it was written.
+    pub kinds: Vec<LitKind>,
     pub kind: ValueKind,
     pub span: Span,
 }
 
+impl ToTokens for Values {
+    fn to_tokens(&self, stream: &mut TokenStream2) {
+        let name = &self.name;
+        let literals = &self.literals;
+
+        stream.extend(match self.kind {
+            ValueKind::Name => quote!(#name),
+            ValueKind::Equals => quote!(#name = #(#literals)*),
+            ValueKind::List | ValueKind::SingleList => quote!(#name(#(#literals),*)),
+        });
+    }
+}
+
 impl Values {
     #[inline]
     pub fn new(name: Ident, kind: ValueKind, literals: Vec<Lit>, span: Span) -> Self {
+        let kinds = literals.iter().map(LitKind::of).collect();
+
+        Self::with_kinds(name, kind, literals, kinds, span)
+    }
+
+    #[inline]
+    pub fn with_kinds(name: Ident, kind: ValueKind, literals: Vec<Lit>, kinds: Vec<LitKind>, span: Span) -> Self {
         Values {
             name,
             literals,
+            kinds,
             kind,
             span,
         }
     }
+
+    /// Fails if the value at `index` is not of one of the `accepted` kinds,
+    /// stating the `expected` type and the kind that was found instead.
+    fn expect(&self, index: usize, accepted: &[LitKind], expected: &str) -> Result<()> {
+        let kind = self.kinds[index];
+
+        if accepted.contains(&kind) {
+            return Ok(());
+        }
+
+        Err(Error::new(
+            self.literals[index].span(),
+            format_args!("expected {}, found {}", expected, kind),
+        ))
+    }
+
+    /// Applies [`expect`] to every value.
+    ///
+    /// [`expect`]: #method.expect
+    fn expect_all(&self, accepted: &[LitKind], expected: &str) -> Result<()> {
+        (0..self.literals.len()).try_for_each(|i| self.expect(i, accepted, expected))
+    }
+}
+
+fn ident_lit(i: &Ident) -> Lit {
+    Lit::Str(LitStr::new(&i.to_string(), i.span()))
+}
+
+fn to_lits(nested: impl IntoIterator<Item = NestedMeta>, span: Span) -> Result<(Vec<Lit>, Vec<LitKind>)> {
+    let mut lits = Vec::new();
+    let mut kinds = Vec::new();
+
+    for meta in nested {
+        match meta {
+            NestedMeta::Lit(l) => {
+                kinds.push(LitKind::of(&l));
+                lits.push(l);
+            }
+            NestedMeta::Meta(m) => match m {
+                Meta::Path(path) => {
+                    let i = to_ident(path)?;
+                    lits.push(ident_lit(&i));
+                    kinds.push(LitKind::Ident);
+                }
+                Meta::List(_) | Meta::NameValue(_) => {
+                    return Err(Error::new(span, "cannot nest a list; only accept literals and identifiers at this level"))
+                }
+            },
+        }
+    }
+
+    Ok((lits, kinds))
+}
+
+#[inline]
+fn list_kind(lits: &[Lit]) -> ValueKind {
+    if lits.len() == 1 {
+        ValueKind::SingleList
+    } else {
+        ValueKind::List
+    }
 }
 
 pub fn parse_values(attr: &Attribute) -> Result<Values> {
@@ -93,33 +567,19 @@ pub fn parse_values(attr: &Attribute) -> Result<Values> {
             let nested = meta.nested;
 
             if nested.is_empty() {
-                return Err(Error::new(attr.span(), "list cannot be empty"));
-            }
-
-            let mut lits = Vec::with_capacity(nested.len());
-
-            for meta in nested {
-                match meta {
-                    NestedMeta::Lit(l) => lits.push(l),
-                    NestedMeta::Meta(m) => match m {
-                        Meta::Path(path) => {
-                            let i = to_ident(path)?;
-                            lits.push(Lit::Str(LitStr::new(&i.to_string(), i.span())))
-                        }
-                        Meta::List(_) | Meta::NameValue(_) => {
-                            return Err(Error::new(attr.span(), "cannot nest a list; only accept literals and identifiers at this level"))
-                        }
-                    },
-                }
+                return Err(Error::new(
+                    attr.span(),
+                    format_args!(
+                        "list cannot be empty; remove `#[{}()]` if it is not needed",
+                        name
+                    ),
+                ));
             }
 
-            let kind = if lits.len() == 1 {
-                ValueKind::SingleList
-            } else {
-                ValueKind::List
-            };
+            let (lits, kinds) = to_lits(nested, attr.span())?;
+            let kind = list_kind(&lits);
 
-            Ok(Values::new(name, kind, lits, attr.span()))
+            Ok(Values::with_kinds(name, kind, lits, kinds, attr.span()))
         }
         Meta::NameValue(meta) => {
             let name = to_ident(meta.path)?;
@@ -130,6 +590,78 @@ pub fn parse_values(attr: &Attribute) -> Result<Values> {
     }
 }
 
+/// The arguments given to a macro attribute itself, e.g. `#[command("name", flag, key = value)]`.
+///
+/// Apart from the optional leading literal, every argument is parsed into [`Values`],
+/// following the same forms as regular option attributes.
+///
+/// [`Values`]: struct.Values.html
+#[derive(Debug, Default)]
+pub struct AttributeArgs {
+    pub name: Option<Lit>,
+    pub values: Vec<Values>,
+}
+
+impl Parse for AttributeArgs {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let mut args = AttributeArgs::default();
+
+        if input.peek(Lit) {
+            args.name = Some(input.parse()?);
+
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        while !input.is_empty() {
+            let name = input.parse::<Ident>()?;
+            let span = name.span();
+
+            let values = if input.peek(Token![=]) {
+                input.parse::<Token![=]>()?;
+
+                let (lit, kind) = if input.peek(Lit) {
+                    let lit = input.parse::<Lit>()?;
+                    let kind = LitKind::of(&lit);
+
+                    (lit, kind)
+                } else {
+                    let i = to_ident(input.parse::<Path>()?)?;
+
+                    (ident_lit(&i), LitKind::Ident)
+                };
+
+                Values::with_kinds(name, ValueKind::Equals, vec![lit], vec![kind], span)
+            } else if input.peek(token::Paren) {
+                let content;
+                parenthesized!(content in input);
+
+                let nested = content.parse_terminated::<_, Token![,]>(NestedMeta::parse)?;
+
+                if nested.is_empty() {
+                    return Err(Error::new(span, "list cannot be empty"));
+                }
+
+                let (lits, kinds) = to_lits(nested, span)?;
+                let kind = list_kind(&lits);
+
+                Values::with_kinds(name, kind, lits, kinds, span)
+            } else {
+                Values::new(name, ValueKind::Name, Vec::new(), span)
+            };
+
+            args.values.push(values);
+
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        Ok(args)
+    }
+}
+
 #[derive(Debug, Clone)]
 struct DisplaySlice<'a, T>(&'a [T]);
 
@@ -164,6 +696,13 @@ fn is_form_acceptable(expect: &[ValueKind], kind: ValueKind) -> bool {
 
 #[inline]
 fn validate(values: &Values, forms: &[ValueKind]) -> Result<()> {
+    if values.kind == ValueKind::Name && !forms.contains(&ValueKind::Name) {
+        return Err(Error::new(
+            values.span,
+            format_args!("`{}` requires a value", values.name),
+        ));
+    }
+
     if !is_form_acceptable(forms, values.kind) {
         return Err(Error::new(
             values.span,
@@ -190,6 +729,7 @@ pub trait AttributeOption: Sized {
 impl AttributeOption for Vec<String> {
     fn parse(values: Values) -> Result<Self> {
         validate(&values, &[ValueKind::List])?;
+        values.expect_all(LitKind::TEXT, "string")?;
 
         Ok(values
             .literals
@@ -203,6 +743,7 @@ impl AttributeOption for String {
     #[inline]
     fn parse(values: Values) -> Result<Self> {
         validate(&values, &[ValueKind::Equals, ValueKind::SingleList])?;
+        values.expect(0, LitKind::TEXT, "string")?;
 
         Ok(values.literals[0].to_str())
     }
@@ -213,14 +754,27 @@ impl AttributeOption for bool {
     fn parse(values: Values) -> Result<Self> {
         validate(&values, &[ValueKind::Name, ValueKind::SingleList])?;
 
-        Ok(values.literals.get(0).map_or(true, |l| l.to_bool()))
+        let lit = match values.literals.first() {
+            Some(lit) => lit,
+            None => return Ok(true),
+        };
+
+        values.expect(0, &[LitKind::Bool, LitKind::Str], "boolean")?;
+
+        match lit {
+            Lit::Str(s) if s.value().parse::<bool>().is_err() => {
+                Err(Error::new(s.span(), format_args!("expected boolean, found \"{}\"", s.value())))
+            }
+            lit => Ok(lit.to_bool()),
+        }
     }
 }
 
 impl AttributeOption for Ident {
     #[inline]
     fn parse(values: Values) -> Result<Self> {
-        validate(&values, &[ValueKind::SingleList])?;
+        validate(&values, &[ValueKind::Equals, ValueKind::SingleList])?;
+        values.expect(0, LitKind::NAME, "identifier")?;
 
         Ok(values.literals[0].to_ident())
     }
@@ -230,22 +784,79 @@ impl AttributeOption for Vec<Ident> {
     #[inline]
     fn parse(values: Values) -> Result<Self> {
         validate(&values, &[ValueKind::List])?;
+        values.expect_all(LitKind::NAME, "identifier")?;
 
         Ok(values.literals.into_iter().map(|l| l.to_ident()).collect())
     }
 }
 
+impl AttributeOption for Vec<u64> {
+    fn parse(values: Values) -> Result<Self> {
+        validate(&values, &[ValueKind::List, ValueKind::SingleList])?;
+        values.expect_all(&[LitKind::Int], "integer")?;
+
+        values
+            .literals
+            .iter()
+            .map(|lit| match lit {
+                Lit::Int(l) => l.base10_parse(),
+                _ => unreachable!("checked by `expect_all`"),
+            })
+            .collect()
+    }
+}
+
+/// Reads a char literal, or a string literal holding a single character.
+fn single_char(lit: &Lit) -> Result<char> {
+    if let Lit::Char(c) = lit {
+        return Ok(c.value());
+    }
+
+    let s = lit.to_str();
+    let mut chars = s.chars();
+
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Ok(c),
+        _ => Err(Error::new(lit.span(), "expected a single character")),
+    }
+}
+
+impl AttributeOption for char {
+    fn parse(values: Values) -> Result<Self> {
+        validate(&values, &[ValueKind::Equals, ValueKind::SingleList])?;
+        values.expect(0, LitKind::TEXT, "character")?;
+
+        single_char(&values.literals[0])
+    }
+}
+
+impl AttributeOption for Vec<char> {
+    fn parse(values: Values) -> Result<Self> {
+        validate(&values, &[ValueKind::List])?;
+        values.expect_all(LitKind::TEXT, "character")?;
+
+        values.literals.iter().map(single_char).collect()
+    }
+}
+
 impl AttributeOption for Option<String> {
     fn parse(values: Values) -> Result<Self> {
         validate(&values, &[ValueKind::Name, ValueKind::Equals, ValueKind::SingleList])?;
 
-        Ok(values.literals.get(0).map(|l| l.to_str()))
+        if values.literals.is_empty() {
+            return Ok(None);
+        }
+
+        values.expect(0, LitKind::TEXT, "string")?;
+
+        Ok(Some(values.literals[0].to_str()))
     }
 }
 
 impl AttributeOption for OnlyIn {
     fn parse(values: Values) -> Result<Self> {
         validate(&values, &[ValueKind::SingleList])?;
+        values.expect(0, LitKind::TEXT, "string")?;
 
         let lit = &values.literals[0];
 
@@ -255,11 +866,26 @@ impl AttributeOption for OnlyIn {
 
 impl AttributeOption for Colour {
     fn parse(values: Values) -> Result<Self> {
-        let span = values.span;
-        let value = String::parse(values)?;
+        validate(&values, &[ValueKind::Equals, ValueKind::SingleList])?;
 
-        Colour::from_str(&value)
-            .ok_or_else(|| Error::new(span, format_args!("invalid colour: \"{}\"", value)))
+        let lit = &values.literals[0];
+
+        if let Lit::Int(n) = lit {
+            return n.base10_parse().map(Colour::Value);
+        }
+
+        values.expect(0, LitKind::TEXT, "colour")?;
+
+        let value = lit.to_str();
+
+        if let Some(colour) = Colour::from_str(&value) {
+            return Ok(colour);
+        }
+
+        LitStr::new(&value, lit.span())
+            .parse()
+            .map(Colour::Const)
+            .map_err(|_| Error::new(lit.span(), format_args!("invalid colour: \"{}\"", value)))
     }
 }
 
@@ -273,6 +899,89 @@ impl AttributeOption for HelpBehaviour {
     }
 }
 
+impl AttributeOption for GroupOrder {
+    fn parse(values: Values) -> Result<Self> {
+        let span = values.span;
+        let value = String::parse(values)?;
+
+        GroupOrder::from_str(&value)
+            .ok_or_else(|| Error::new(span, format_args!("invalid group order: \"{}\"", value)))
+    }
+}
+
+impl AttributeOption for StaticCase {
+    fn parse(values: Values) -> Result<Self> {
+        let span = values.span;
+        let value = String::parse(values)?;
+
+        StaticCase::from_str(&value)
+            .ok_or_else(|| Error::new(span, format_args!("invalid static case: \"{}\"", value)))
+    }
+}
+
+impl AttributeOption for HelpText {
+    fn parse(values: Values) -> Result<Self> {
+        let span = values.span;
+        let value = String::parse(values)?;
+
+        HelpText::validate(&value, span)?;
+
+        Ok(HelpText(value))
+    }
+}
+
+impl AttributeOption for CollapsedText {
+    fn parse(values: Values) -> Result<Self> {
+        validate(&values, &[ValueKind::Equals, ValueKind::List])?;
+        values.expect_all(LitKind::TEXT, "string")?;
+
+        if values.literals.is_empty() || values.literals.len() > 2 {
+            return Err(Error::new(
+                values.span,
+                "expected the singular text, optionally followed by the plural one",
+            ));
+        }
+
+        let texts = values.literals.iter().map(|lit| lit.to_str()).collect::<Vec<_>>();
+
+        for text in &texts {
+            CollapsedText::validate(text, values.span)?;
+        }
+
+        let singular = texts[0].clone();
+        let plural = texts.last().unwrap().clone();
+
+        Ok(CollapsedText(singular, plural))
+    }
+}
+
+impl AttributeOption for ArgsBound {
+    fn parse(values: Values) -> Result<Self> {
+        validate(&values, &[ValueKind::SingleList])?;
+
+        let lit = &values.literals[0];
+
+        if let Lit::Str(s) = lit {
+            let value = s.value();
+
+            if value.parse::<u16>().is_err() && syn::parse_str::<Ident>(&value).is_ok() {
+                return Ok(ArgsBound::Const(lit.to_ident()));
+            }
+        }
+
+        u16::parse(values).map(ArgsBound::Num)
+    }
+}
+
+impl AttributeOption for Preset {
+    fn parse(values: Values) -> Result<Self> {
+        validate(&values, &[ValueKind::Equals, ValueKind::SingleList])?;
+        values.expect(0, LitKind::NAME, "identifier")?;
+
+        Ok(Preset(values.literals[0].to_ident()))
+    }
+}
+
 impl AttributeOption for Checks {
     #[inline]
     fn parse(values: Values) -> Result<Self> {
@@ -311,6 +1020,7 @@ macro_rules! attr_option_num {
             impl AttributeOption for $n {
                 fn parse(values: Values) -> Result<Self> {
                     validate(&values, &[ValueKind::SingleList])?;
+                    values.expect(0, LitKind::NUMBER, "integer")?;
 
                     Ok(match &values.literals[0] {
                         Lit::Int(l) => l.base10_parse::<$n>()?,
@@ -337,4 +1047,417 @@ macro_rules! attr_option_num {
     }
 }
 
-attr_option_num!(u16, u32, usize);
+attr_option_num!(u16, u32, u64, usize);
+
+#[cfg(test)]
+mod test {
+    use super::{parse_help_unavailable_reason, parse_keyed_example, parse_localized, parse_metrics, parse_signed, parse_values, AttributeArgs, AttributeOption, LitKind, ValueKind};
+    use crate::structures::{ArgsBound, Colour, Example, GroupOrder, Metrics, Permissions};
+    use crate::util::{AsOption, LitExt};
+    use quote::{quote, ToTokens};
+    use syn::{parse::Parser, parse_quote, Attribute, Ident};
+
+    #[test]
+    fn empty_lists() {
+        let attrs: Vec<Attribute> = vec![
+            parse_quote!(#[checks()]),
+            parse_quote!(#[aliases()]),
+            parse_quote!(#[allowed_roles()]),
+            parse_quote!(#[sub_commands()]),
+        ];
+
+        for attr in &attrs {
+            let err = parse_values(attr).unwrap_err().to_string();
+            assert!(err.contains("list cannot be empty"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn args_bound() {
+        let attr: Attribute = parse_quote!(#[min_args(MY_MIN)]);
+        match ArgsBound::parse(parse_values(&attr).unwrap()).unwrap() {
+            ArgsBound::Const(c) => assert_eq!(c, "MY_MIN"),
+            b => panic!("expected a constant, got {:?}", b),
+        }
+
+        let attr: Attribute = parse_quote!(#[min_args(3)]);
+        match ArgsBound::parse(parse_values(&attr).unwrap()).unwrap() {
+            ArgsBound::Num(n) => assert_eq!(n, 3),
+            b => panic!("expected a number, got {:?}", b),
+        }
+    }
+
+    #[test]
+    fn zero_args_bound() {
+        let attr: Attribute = parse_quote!(#[max_args(0)]);
+        let bound = AsOption(Some(ArgsBound::parse(parse_values(&attr).unwrap()).unwrap()));
+
+        assert_eq!(bound.into_token_stream().to_string(), "Some (0u16)");
+    }
+
+    #[test]
+    fn wide_integers() {
+        let attr: Attribute = parse_quote!(#[min_membership_seconds(604800)]);
+        let n = Option::<u64>::parse(parse_values(&attr).unwrap()).unwrap();
+
+        assert_eq!(n, Some(604_800));
+    }
+
+    #[test]
+    fn integer_lists() {
+        let attr: Attribute = parse_quote!(#[guilds(123, 456)]);
+        let ids = Vec::<u64>::parse(parse_values(&attr).unwrap()).unwrap();
+        assert_eq!(ids, [123, 456]);
+
+        let attr: Attribute = parse_quote!(#[guilds(123, "456")]);
+        let err = Vec::<u64>::parse(parse_values(&attr).unwrap()).unwrap_err();
+        assert_eq!(err.to_string(), "expected integer, found string literal");
+
+        let attr: Attribute = parse_quote!(#[allowed_users(123, foo)]);
+        let err = Vec::<u64>::parse(parse_values(&attr).unwrap()).unwrap_err();
+        assert_eq!(err.to_string(), "expected integer, found identifier");
+    }
+
+    #[test]
+    fn cooldown_bypass_roles() {
+        let attr: Attribute = parse_quote!(#[cooldown_bypass_roles("Mod", Admin)]);
+        let roles = Vec::<String>::parse(parse_values(&attr).unwrap()).unwrap();
+        assert_eq!(roles, ["Mod", "Admin"]);
+
+        let attr: Attribute = parse_quote!(#[cooldown_bypass_role_ids(123)]);
+        let ids = Vec::<u64>::parse(parse_values(&attr).unwrap()).unwrap();
+        assert_eq!(ids, [123]);
+
+        let attr: Attribute = parse_quote!(#[cooldown_bypass_role_ids("Mod")]);
+        let err = Vec::<u64>::parse(parse_values(&attr).unwrap()).unwrap_err();
+        assert_eq!(err.to_string(), "expected integer, found string literal");
+    }
+
+    #[test]
+    fn literal_kinds() {
+        let attr: Attribute = parse_quote!(#[aliases("a", b, 'c', 1)]);
+        let values = parse_values(&attr).unwrap();
+        assert_eq!(values.kinds, [LitKind::Str, LitKind::Ident, LitKind::Char, LitKind::Int]);
+
+        let args: AttributeArgs = syn::parse_str("key = value, other = 1.5").unwrap();
+        assert_eq!(args.values[0].kinds, [LitKind::Ident]);
+        assert_eq!(args.values[1].kinds, [LitKind::Float]);
+    }
+
+    #[test]
+    fn mismatched_literals() {
+        let attr: Attribute = parse_quote!(#[usage(42)]);
+        let err = String::parse(parse_values(&attr).unwrap()).unwrap_err();
+        assert_eq!(err.to_string(), "expected string, found integer literal");
+
+        let attr: Attribute = parse_quote!(#[aliases("a", true)]);
+        let err = Vec::<String>::parse(parse_values(&attr).unwrap()).unwrap_err();
+        assert_eq!(err.to_string(), "expected string, found boolean literal");
+
+        let attr: Attribute = parse_quote!(#[max_args(1.5)]);
+        let err = u16::parse(parse_values(&attr).unwrap()).unwrap_err();
+        assert_eq!(err.to_string(), "expected integer, found float literal");
+
+        let attr: Attribute = parse_quote!(#[owners_only(1)]);
+        let err = bool::parse(parse_values(&attr).unwrap()).unwrap_err();
+        assert_eq!(err.to_string(), "expected boolean, found integer literal");
+
+        let attr: Attribute = parse_quote!(#[owners_only("yes")]);
+        let err = bool::parse(parse_values(&attr).unwrap()).unwrap_err();
+        assert_eq!(err.to_string(), "expected boolean, found \"yes\"");
+
+        let attr: Attribute = parse_quote!(#[sub_commands(foo, 2)]);
+        let err = Vec::<Ident>::parse(parse_values(&attr).unwrap()).unwrap_err();
+        assert_eq!(err.to_string(), "expected identifier, found integer literal");
+    }
+
+    #[test]
+    fn bare_flags() {
+        let attr: Attribute = parse_quote!(#[description]);
+        let err = String::parse(parse_values(&attr).unwrap()).unwrap_err();
+        assert_eq!(err.to_string(), "`description` requires a value");
+
+        let attr: Attribute = parse_quote!(#[sub_commands]);
+        let err = Vec::<Ident>::parse(parse_values(&attr).unwrap()).unwrap_err();
+        assert_eq!(err.to_string(), "`sub_commands` requires a value");
+
+        let attr: Attribute = parse_quote!(#[owners_only]);
+        assert!(bool::parse(parse_values(&attr).unwrap()).unwrap());
+
+        let attr: Attribute = parse_quote!(#[usage]);
+        assert_eq!(Option::<String>::parse(parse_values(&attr).unwrap()).unwrap(), None);
+    }
+
+    #[test]
+    fn localized_names() {
+        let attr: Attribute = parse_quote!(#[localized_names(en = "add", fr = "ajouter", pt_br = "somar")]);
+        let names = parse_localized(&attr, "name").unwrap();
+        assert_eq!(names, [
+            ("en".to_string(), "add".to_string()),
+            ("fr".to_string(), "ajouter".to_string()),
+            ("pt_br".to_string(), "somar".to_string()),
+        ]);
+
+        let attr: Attribute = parse_quote!(#[localized_names(en = "add", en = "plus")]);
+        let err = parse_localized(&attr, "name").unwrap_err();
+        assert_eq!(err.to_string(), "the locale `en` is specified more than once");
+
+        let attr: Attribute = parse_quote!(#[localized_names(EN = "add")]);
+        let err = parse_localized(&attr, "name").unwrap_err();
+        assert_eq!(err.to_string(), "locales must be lowercase ASCII, such as `en` or `pt_br`");
+
+        let attr: Attribute = parse_quote!(#[localized_names("add")]);
+        let err = parse_localized(&attr, "name").unwrap_err();
+        assert_eq!(err.to_string(), "expected `locale = \"name\"`");
+
+        let attr: Attribute = parse_quote!(#[localized_usage = "<a> <b>"]);
+        let err = parse_localized(&attr, "usage").unwrap_err();
+        assert_eq!(err.to_string(), "expected `#[localized_usage(locale = \"usage\", ...)]`");
+
+        let attr: Attribute = parse_quote!(#[localized_usage(en = "<a> <b>", fr = "<a> <b> [c]")]);
+        let usages = parse_localized(&attr, "usage").unwrap();
+        assert_eq!(usages[1], ("fr".to_string(), "<a> <b> [c]".to_string()));
+    }
+
+    #[test]
+    fn help_unavailable_reason() {
+        let attr: Attribute = parse_quote!(#[help_available(false, reason = "staff only")]);
+        assert_eq!(parse_help_unavailable_reason(&attr).unwrap().as_deref(), Some("staff only"));
+
+        let attrs: Vec<Attribute> = vec![
+            parse_quote!(#[help_available]),
+            parse_quote!(#[help_available(false)]),
+        ];
+
+        for attr in &attrs {
+            assert_eq!(parse_help_unavailable_reason(attr).unwrap(), None);
+            assert!(bool::parse(parse_values(attr).unwrap()).is_ok());
+        }
+
+        let attr: Attribute = parse_quote!(#[help_available(true, reason = "staff only")]);
+        let err = parse_help_unavailable_reason(&attr).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "a reason is only given to hidden commands, as in `#[help_available(false, reason = \"...\")]`"
+        );
+
+        let attr: Attribute = parse_quote!(#[help_available(false, why = "staff only")]);
+        let err = parse_help_unavailable_reason(&attr).unwrap_err();
+        assert_eq!(err.to_string(), "expected `reason`");
+
+        let attr: Attribute = parse_quote!(#[help_available(false, reason = "")]);
+        let err = parse_help_unavailable_reason(&attr).unwrap_err();
+        assert_eq!(err.to_string(), "expected a non-empty string");
+    }
+
+    #[test]
+    fn metrics() {
+        let attr: Attribute = parse_quote!(#[metrics(name = "ban", tags = ["mod", "slow", "mod"])]);
+        let metrics = parse_metrics(&attr).unwrap();
+        assert_eq!(metrics, Metrics {
+            name: "ban".to_string(),
+            tags: vec!["mod".to_string(), "slow".to_string()],
+        });
+        assert_eq!(
+            AsOption(Some(metrics)).into_token_stream().to_string(),
+            "Some (serenity :: framework :: standard :: CommandMetrics { name : \"ban\" , tags : & [\"mod\" , \"slow\"] , })"
+        );
+
+        let attr: Attribute = parse_quote!(#[metrics(name = "ping")]);
+        assert!(parse_metrics(&attr).unwrap().tags.is_empty());
+
+        let attr: Attribute = parse_quote!(#[metrics(tags = ["mod"])]);
+        let err = parse_metrics(&attr).unwrap_err();
+        assert_eq!(err.to_string(), "`metrics` requires a `name`");
+
+        let attr: Attribute = parse_quote!(#[metrics(name = "a", name = "b")]);
+        let err = parse_metrics(&attr).unwrap_err();
+        assert_eq!(err.to_string(), "`name` is specified more than once");
+
+        let attr: Attribute = parse_quote!(#[metrics(name = "a", labels = ["b"])]);
+        let err = parse_metrics(&attr).unwrap_err();
+        assert_eq!(err.to_string(), "expected `name` or `tags`");
+
+        let attr: Attribute = parse_quote!(#[metrics(name = "a", tags = [""])]);
+        assert!(parse_metrics(&attr).is_err());
+
+        let attr: Attribute = parse_quote!(#[metrics = "a"]);
+        assert!(parse_metrics(&attr).is_err());
+    }
+
+    #[test]
+    fn single_characters() {
+        let attr: Attribute = parse_quote!(#[delimiter_escape("\\")]);
+        assert_eq!(char::parse(parse_values(&attr).unwrap()).unwrap(), '\\');
+
+        let attr: Attribute = parse_quote!(#[delimiter_escape = '^']);
+        assert_eq!(char::parse(parse_values(&attr).unwrap()).unwrap(), '^');
+
+        let attr: Attribute = parse_quote!(#[delimiter_escape("\\\\")]);
+        let err = char::parse(parse_values(&attr).unwrap()).unwrap_err();
+        assert_eq!(err.to_string(), "expected a single character");
+
+        let attr: Attribute = parse_quote!(#[delimiter_escape("")]);
+        assert!(char::parse(parse_values(&attr).unwrap()).is_err());
+
+        let attr: Attribute = parse_quote!(#[delimiter_escape(1)]);
+        let err = char::parse(parse_values(&attr).unwrap()).unwrap_err();
+        assert_eq!(err.to_string(), "expected character, found integer literal");
+    }
+
+    #[test]
+    fn signed_integers() {
+        let attr: Attribute = parse_quote!(#[order(3)]);
+        assert_eq!(parse_signed(&attr).unwrap(), 3);
+
+        let attr: Attribute = parse_quote!(#[order(-10)]);
+        assert_eq!(parse_signed(&attr).unwrap(), -10);
+
+        let attr: Attribute = parse_quote!(#[order = -2147483648]);
+        assert_eq!(parse_signed(&attr).unwrap(), -2_147_483_648);
+
+        let attr: Attribute = parse_quote!(#[order(2147483648)]);
+        assert_eq!(parse_signed(&attr).unwrap_err().to_string(), "expected a 32-bit, signed integer");
+
+        let attr: Attribute = parse_quote!(#[order("1")]);
+        assert!(parse_signed(&attr).is_err());
+
+        let attr: Attribute = parse_quote!(#[order(1, 2)]);
+        assert!(parse_signed(&attr).is_err());
+    }
+
+    #[test]
+    fn group_orders() {
+        let attr: Attribute = parse_quote!(#[sort_groups_by("weight")]);
+        assert_eq!(GroupOrder::parse(parse_values(&attr).unwrap()).unwrap(), GroupOrder::Weight);
+
+        let attr: Attribute = parse_quote!(#[sort_groups_by = "Name"]);
+        assert_eq!(GroupOrder::parse(parse_values(&attr).unwrap()).unwrap(), GroupOrder::Name);
+
+        let attr: Attribute = parse_quote!(#[sort_groups_by("size")]);
+        let err = GroupOrder::parse(parse_values(&attr).unwrap()).unwrap_err();
+        assert_eq!(err.to_string(), "invalid group order: \"size\"");
+    }
+
+    #[test]
+    fn colours() {
+        let attr: Attribute = parse_quote!(#[embed_error_colour(RED)]);
+        assert_eq!(Colour::parse(parse_values(&attr).unwrap()).unwrap(), Colour::Value(0xE74C3C));
+
+        let attr: Attribute = parse_quote!(#[embed_error_colour(0x123456)]);
+        assert_eq!(Colour::parse(parse_values(&attr).unwrap()).unwrap(), Colour::Value(0x123456));
+
+        let attr: Attribute = parse_quote!(#[embed_error_colour(MY_COLOUR)]);
+        match Colour::parse(parse_values(&attr).unwrap()).unwrap() {
+            Colour::Const(path) => assert!(path.is_ident("MY_COLOUR")),
+            c => panic!("expected a constant, got {:?}", c),
+        }
+
+        let attr: Attribute = parse_quote!(#[embed_error_colour("crate::colours::MINE")]);
+        match Colour::parse(parse_values(&attr).unwrap()).unwrap() {
+            Colour::Const(path) => assert_eq!(path.segments.len(), 3),
+            c => panic!("expected a constant, got {:?}", c),
+        }
+
+        let attr: Attribute = parse_quote!(#[embed_error_colour("not a colour")]);
+        let err = Colour::parse(parse_values(&attr).unwrap()).unwrap_err();
+        assert_eq!(err.to_string(), "invalid colour: \"not a colour\"");
+    }
+
+    #[test]
+    fn keyed_examples() {
+        let attr: Attribute = parse_quote!(#[example(input = "2 2", output = "4")]);
+        let example = parse_keyed_example(&attr).unwrap().unwrap();
+        assert_eq!(example, Example { input: "2 2".to_string(), output: "4".to_string(), ..Example::default() });
+
+        let attr: Attribute = parse_quote!(#[example(input = "2 2")]);
+        let example = parse_keyed_example(&attr).unwrap().unwrap();
+        assert_eq!(example.output, "");
+
+        let attr: Attribute = parse_quote!(#[example(output = "4")]);
+        let err = parse_keyed_example(&attr).unwrap_err();
+        assert_eq!(err.to_string(), "a keyed example requires an `input`");
+
+        let attr: Attribute = parse_quote!(#[example(input = "2 2", result = "4")]);
+        let err = parse_keyed_example(&attr).unwrap_err();
+        assert_eq!(err.to_string(), "expected `input`, `output` or `requires`");
+    }
+
+    #[test]
+    fn example_permissions() {
+        let attr: Attribute = parse_quote!(#[example("!ban @user", requires = BAN_MEMBERS)]);
+        let example = parse_keyed_example(&attr).unwrap().unwrap();
+        assert_eq!(example.input, "!ban @user");
+        assert_eq!(example.requires, Permissions(0b100));
+
+        let attr: Attribute = parse_quote!(#[example(input = "!purge 10", output = "Purged.", requires = KICK | MANAGE_MESSAGES)]);
+        let example = parse_keyed_example(&attr).unwrap().unwrap();
+        assert_eq!(example.output, "Purged.");
+        assert_eq!(example.requires, Permissions(0b10 | 0x2000));
+        assert_eq!(example.into_token_stream().to_string(), quote!(("!purge 10", "Purged.")).to_string());
+
+        let attr: Attribute = parse_quote!(#[example(input = "2 2")]);
+        assert_eq!(parse_keyed_example(&attr).unwrap().unwrap().requires, Permissions(0));
+
+        let attr: Attribute = parse_quote!(#[example("!ban @user", requires = BANISH)]);
+        let err = parse_keyed_example(&attr).unwrap_err();
+        assert_eq!(err.to_string(), "invalid permission");
+
+        let attr: Attribute = parse_quote!(#[example("!ban @user", requires = BAN, requires = KICK)]);
+        let err = parse_keyed_example(&attr).unwrap_err();
+        assert_eq!(err.to_string(), "specified more than once");
+
+        let attr: Attribute = parse_quote!(#[example("!ban @user", input = "!ban")]);
+        let err = parse_keyed_example(&attr).unwrap_err();
+        assert_eq!(err.to_string(), "specified more than once");
+
+        let attr: Attribute = parse_quote!(#[example(requires = BAN)]);
+        let err = parse_keyed_example(&attr).unwrap_err();
+        assert_eq!(err.to_string(), "a keyed example requires an `input`");
+    }
+
+    #[test]
+    fn positional_examples() {
+        let attrs: Vec<Attribute> = vec![parse_quote!(#[example("2 2")]), parse_quote!(#[example = "2 2"])];
+
+        for attr in &attrs {
+            assert!(parse_keyed_example(attr).unwrap().is_none());
+            assert_eq!(String::parse(parse_values(attr).unwrap()).unwrap(), "2 2");
+        }
+    }
+
+    #[test]
+    fn attribute_args() {
+        let args: AttributeArgs = syn::parse_str(r#""foo", fast_lookup, key = value, list("a", b)"#).unwrap();
+
+        assert_eq!(args.name.unwrap().to_str(), "foo");
+
+        let kinds = args.values.iter().map(|v| (v.name.to_string(), v.kind)).collect::<Vec<_>>();
+        assert_eq!(kinds, vec![
+            ("fast_lookup".to_string(), ValueKind::Name),
+            ("key".to_string(), ValueKind::Equals),
+            ("list".to_string(), ValueKind::List),
+        ]);
+        assert_eq!(args.values[1].literals[0].to_str(), "value");
+    }
+
+    #[test]
+    fn attribute_args_without_name() {
+        let args: AttributeArgs = syn::parse_str("fast_lookup").unwrap();
+
+        assert!(args.name.is_none());
+        assert_eq!(args.values.len(), 1);
+    }
+
+    #[test]
+    fn allowed_users_option() {
+        let attr: Attribute = parse_quote!(#[allowed_users(123, 456)]);
+        let ids = Vec::<u64>::parse(parse_values(&attr).unwrap()).unwrap();
+        assert_eq!(quote!(&[#(#ids),*]).to_string(), "& [123u64 , 456u64]");
+
+        let attrs = Attribute::parse_outer.parse_str("#[allowed_users(123, foo)]").unwrap();
+        let err = Vec::<u64>::parse(parse_values(&attrs[0]).unwrap()).unwrap_err();
+        let start = err.span().start();
+        assert_eq!((start.line, start.column), (1, 21));
+    }
+}
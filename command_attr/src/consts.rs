@@ -1,6 +1,8 @@
 pub mod suffixes {
     pub const COMMAND: &str = "COMMAND";
     pub const COMMAND_OPTIONS: &str = "COMMAND_OPTIONS";
+    pub const COMMAND_LOOKUP: &str = "COMMAND_LOOKUP";
+    pub const COMMAND_MARKER: &str = "COMMAND_MARKER";
     pub const HELP_OPTIONS: &str = "_OPTIONS";
     pub const GROUP: &str = "GROUP";
     pub const GROUP_OPTIONS: &str = "GROUP_OPTIONS";
@@ -8,3 +10,38 @@ pub mod suffixes {
 }
 
 pub use self::suffixes::*;
+
+/// Discord's limit on the amount of fields in an embed.
+pub const EMBED_MAX_FIELDS: usize = 25;
+
+/// Discord's limit on the length of an embed field's value.
+pub const EMBED_MAX_FIELD_LENGTH: usize = 1024;
+
+/// Tokens that may appear in help text options, wrapped in braces (e.g. `{prefix}`).
+/// They are substituted by the help command when it runs.
+pub const HELP_TEXT_TOKENS: &[&str] = &["prefix", "command"];
+
+/// Tokens that may appear in `collapsed_commands_text`, substituted by the help command.
+pub const COLLAPSED_TEXT_TOKENS: &[&str] = &["count"];
+
+/// Fields of `CommandOptions` holding an `Option`. `assert_command_options!` wraps their
+/// expected values in `Some`, unless they are written as `Some(...)` or `None`.
+pub const OPTIONAL_COMMAND_FIELDS: &[&str] = &[
+    "bucket",
+    "desc",
+    "category",
+    "usage",
+    "min_args",
+    "min_membership_seconds",
+    "timeout",
+    "max_args",
+    "base_min_args",
+    "error_handler",
+    "unknown_sub",
+    "num_delimiters",
+    "delimiter_escape",
+    "deprecation_notice",
+    "help_unavailable_reason",
+    "metrics",
+    "required_feature",
+];
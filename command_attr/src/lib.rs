@@ -8,8 +8,9 @@ extern crate proc_macro;
 
 use proc_macro::TokenStream;
 use proc_macro2::Span;
-use quote::quote;
+use quote::{format_ident, quote, ToTokens};
 use syn::{
+    ext::IdentExt,
     parse::{Error, Parse, ParseStream, Result},
     parse_macro_input, parse_quote,
     punctuated::Punctuated,
@@ -65,30 +66,83 @@ macro_rules! match_options {
 /// | Syntax                                                                       | Description                                                                                              | Argument explanation                                                                                                                                                                                                             |
 /// | ---------------------------------------------------------------------------- | -------------------------------------------------------------------------------------------------------- | -------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------- |
 /// | `#[checks(identifiers)]`                                                     | Preconditions that must met before the command's execution.                                              | `identifiers` is a comma separated list of identifiers referencing functions marked by the `#[check]` macro                                                                                                                      |
-/// | `#[aliases(names)]`                                                          | Alternative names to refer to this command.                                                              | `names` is a comma separated list of desired aliases.                                                                                                                                                                             |
-/// | `#[description(desc)]` </br> `#[description = desc]`                         | The command's description or summary.                                                                    | `desc` is a string describing the command.                                                                                                                                                                                       |
-/// | `#[usage(use)]` </br> `#[usage = use]`                                       | The command's intended usage.                                                                            | `use` is a string stating the schema for the command's usage.                                                                                                                                                                    |
-/// | `#[example(ex)]` </br> `#[example = ex]`                                     | An example of the command's usage. May be called multiple times to add many examples at once.            | `ex` is a string                                                                                                                                                                                                                 |
+/// | `#[priority_checks(identifiers)]`                                            | Preconditions evaluated before the group's and the command's `checks`.                                   | `identifiers` is a comma separated list of identifiers referencing functions marked by the `#[check]` macro                                                                                                                      |
+/// | `#[inherit_group_checks]` </br> `#[inherit_group_checks(b)]`                 | Whether the checks of the command's group also apply to the command. Defaults to `true`.                 | `b` is a boolean. If no boolean is provided, the value is assumed to be `true`.                                                                                                                                                  |
+/// | `#[checks_ordered]`                                                          | States that the order of the checks matters. This is already the default, so the option is purely informative. | Takes no value.                                                                                                                                                                                                                  |
+/// | `#[aliases(names)]`                                                          | Alternative names to refer to this command.                                                              | `names` is a comma separated list of desired aliases. Repeated names are kept once.                                                                                                                                                                          |
+/// | `#[localized_names(locale = name, ...)]`                                   | Translations of the command's name. The command can be invoked by any of them, like an alias.            | `locale` is a lowercase ASCII identifier, such as `en` or `pt_br`, given at most once. `name` is a string.                                                                                                                    |
+/// | `#[localized_usage(locale = usage, ...)]`                                  | Translations of the command's usage. A help whose `locale` matches one of them shows it instead of `usage`. | `locale` is a lowercase ASCII identifier, such as `en` or `pt_br`, given at most once. `usage` is a string.                                                                                                                   |
+/// | `#[prefixes(prefs)]`                                                         | Prefixes that invoke this command on their own, in addition to the framework's configured prefixes. Unlike aliases, these precede the command's name, e.g. `?ping`. A command's prefix does not invoke other commands. | `prefs` is a comma separated list of non-empty strings.                                                                                                                                                                       |
+/// | `#[description(desc)]` </br> `#[description = desc]` </br> `#[description(include = path)]` </br> `#[description(env = var)]` | The command's description or summary.                         | `desc` is a string describing the command.</br> `path` is a string literal path to a file, relative to the current source file, whose contents are included at compile time with `include_str!`.</br> `var` is a string literal naming an environment variable, read at compile time with `env!`. The build fails if it is not set.                                                                                                                                                                                    |
+/// | `#[category(name)]` </br> `#[category = name]`                               | The category under which the help command lists this command, instead of its group. Does not affect dispatch. | `name` is a string.                                                                                                                                                                                                      |
+/// | `#[usage(use)]` </br> `#[usage = use]`                                       | The command's intended usage. It is also split into `usage_parts`, with `<arg>` as a required argument and `[arg]` as an optional one. | `use` is a string stating the schema for the command's usage. Unbalanced brackets raise a warning.                                                                                                                               |
+/// | `#[example(ex)]` </br> `#[example = ex]` </br> `#[example(input = ex, output = out)]` </br> `#[example(ex, requires = perms)]` | An example of the command's usage. May be called multiple times to add many examples at once.            | `ex` is a string of the arguments.</br> `out` is a string of the output the example is expected to produce, shown alongside it in the help; it is empty if not given.</br> `perms` is a `|` separated list of permission names, accepted by either keyed form. The help only shows the example to users with all of them. |
 /// | `#[delimiters(delims)]`                                                      | Argument delimiters specific to this command. Overrides the global list of delimiters in the framework.  | `delims` is a comma separated list of strings |
-/// | `#[min_args(min)]` </br> `#[max_args(max)]` </br> `#[num_args(min_and_max)]` | The expected length of arguments that the command must receive in order to function correctly.           | `min`, `max` and `min_and_max` are 16-bit, unsigned integers.                                                                                                                                                                    |
-/// | `#[required_permissions(perms)]`                                             | Set of permissions the user must possess.                                                                | `perms` is a comma separated list of permission names.</br> These can be found at [Discord's official documentation](https://discord.com/developers/docs/topics/permissions).                                                 |
+/// | `#[min_args(min)]` </br> `#[max_args(max)]` </br> `#[num_args(min_and_max)]` | The expected length of arguments that the command must receive in order to function correctly.           | `min`, `max` and `min_and_max` are 16-bit, unsigned integers, or identifiers of `u16` constants in scope.                                                                                                                       |
+/// | `#[base_min_args(min)]`                                                      | The minimum amount of arguments when the command is invoked itself, rather than one of its `sub_commands`. Takes precedence over `min_args` in that case; invoked sub commands apply their own options. | `min` is a 16-bit, unsigned integer, or an identifier of a `u16` constant in scope.                                                                                                                             |
+/// | `#[num_delimiters(n)]`                                                       | The exact amount of delimiters the command's input must contain.                                         | `n` is a 16-bit, unsigned integer. Requires `#[delimiters]` to be set on the command.                                                                                                                                            |
+/// | `#[delimiter_escape(c)]` </br> `#[delimiter_escape = c]`                   | A character that makes the character following it literal in an unquoted argument, so an argument may contain a delimiter. The escape character itself is removed from the argument. | `c` is a string or char literal holding a single character, such as `"\\"`.                                                                                                                                     |
+/// | `#[quotes(chars)]`                                                          | The characters that surround a quoted argument, which then ends at the next occurrence of the same character. Replaces the framework's `"`; an empty list disables quoted arguments. | `chars` is a comma separated list of string or char literals, each holding a single character, such as `"`", "'"`. Repeated characters are kept once. |
+/// | `#[min_membership_seconds(n)]`                                               | How long the user must have been a member of the guild to use the command. Enforced by the framework at dispatch, using the cache. | `n` is a 64-bit, unsigned integer, in seconds.                                                                                                                                                                             |
+/// | `#[timeout(n)]`                                                              | How long the command may run. Once it elapses, the command's future is dropped, cancelling it at its current `.await`, and the command ends with an error wrapping `tokio::time::Elapsed`. Defaults to no timeout. | `n` is a 64-bit, unsigned integer, in seconds.                                                                                                                                                                             |
+/// | `#[required_permissions(perms)]`                                             | Set of permissions the user must possess.                                                                | `perms` is a comma separated list of permission names.</br> These can be found at [Discord's official documentation](https://discord.com/developers/docs/topics/permissions). The shorthands `ADMIN`, `KICK`, `BAN`, `MUTE`, `DEAFEN`, `MOVE` and `MANAGE_SERVER` are also accepted.                                                 |
 /// | `#[allowed_roles(roles)]`                                                    | Set of roles the user must possess.                                                                      | `roles` is a comma separated list of role names.                                                                                                                                                                                 |
-/// | `#[help_available]` </br> `#[help_available(b)]`                             | If the command should be displayed in the help message.                                                  | `b` is a boolean. If no boolean is provided, the value is assumed to be `true`.                                                                                                                                                  |
-/// | `#[only_in(ctx)]`                                                            | Which environment the command can be executed in.                                                        | `ctx` is a string with the accepted values `guild`/`guilds` and `dm`/`dms` (Direct Message).                                                                                                                                     |
+/// | `#[allowed_users(ids)]`                                                      | Users the command is restricted to. If empty, anyone may use the command. Owners with privilege bypass the restriction. | `ids` is a comma separated list of user IDs, as integers.                                                                                                                                                                   |
+/// | `#[guilds(ids)]`                                                             | Guilds the command is restricted to. If empty, the command may be used in any guild. Owners with privilege bypass the restriction. | `ids` is a comma separated list of guild IDs, as integers.                                                                                                                                                                  |
+/// | `#[slash_compatible]` </br> `#[slash_compatible(b)]`                         | Declare the command usable as a slash command. Its name, aliases and localised names are then checked to be valid slash command names: 1 to 32 lowercase letters, digits, `-` or `_`. | `b` is a boolean. If no boolean is provided, the value is assumed to be `true`.                                                                                                                                                  |
+/// | `#[help_available]` </br> `#[help_available(b)]` </br> `#[help_available(false, reason = r)]` | If the command should be displayed in the help message.                                                  | `b` is a boolean. If no boolean is provided, the value is assumed to be `true`.</br> `r` is a non-empty string explaining why the command is hidden, meant for privileged users such as moderators. |
+/// | `#[only_in(ctx)]`                                                            | Which environment the command can be executed in. A command's own restriction overrides the one of its parent command or group; without one, or with `inherit`, that one applies. | `ctx` is a string with the accepted values `guild`/`guilds`, `dm`/`dms` (Direct Message), `both` and `inherit`.                                                                                                  |
+/// | `#[delete_invocation]` </br> `#[delete_invocation(b)]`                       | If the framework should delete the message that invoked the command before running it. The deletion is skipped if the bot lacks the permission to delete the message. | `b` is a boolean. If no boolean is provided, the value is assumed to be `true`.                                                                                                                                   |
+/// | `#[typing]` </br> `#[typing(b)]`                                             | If the framework should show the bot as typing in the channel before running the command, for commands that take a while to respond. | `b` is a boolean. If no boolean is provided, the value is assumed to be `true`.                                                                                                                                   |
+/// | `#[requires_voice]` </br> `#[requires_voice(b)]`                             | If the user must be in a voice channel of the guild to use the command. Enforced by the framework at dispatch, using the cache, as the macro cannot know voice states. | `b` is a boolean. If no boolean is provided, the value is assumed to be `true`.                                                                                                                                   |
+/// | `#[requires_age_gated_guild]` </br> `#[requires_age_gated_guild(b)]`         | If the command may only be used in age-restricted guilds. Not enforced by the framework, which cannot tell whether a guild is age-restricted; checks may read it from the `CommandOptions` they are given. | `b` is a boolean. If no boolean is provided, the value is assumed to be `true`.                                                                                                                                   |
 /// | `#[bucket(name)]` </br> `#[bucket = name]`                                   | What bucket will impact this command.                                                                    | `name` is a string containing the bucket's name.</br> Refer to [the bucket example in the standard framework](https://docs.rs/serenity/*/serenity/framework/standard/struct.StandardFramework.html#method.bucket) for its usage. |
+/// | `#[cooldown_bypass_roles(roles)]` </br> `#[cooldown_bypass_role_ids(ids)]`    | Roles whose members are not limited by the command's bucket. Requires the command's own `#[bucket]`, which may repeat its group's. | `roles` is a comma separated list of role names. `ids` is a comma separated list of role IDs, as integers. Repeated entries are kept once.                                                                      |
 /// | `#[owners_only]` </br> `#[owners_only(b)]`                                   | If this command is exclusive to owners.                                                                  | `b` is a boolean. If no boolean is provided, the value is assumed to be `true`.                                                                                                                                                  |
 /// | `#[owner_privilege]` </br> `#[owner_privilege(b)]`                           | If owners can bypass certain options.                                                                    | `b` is a boolean. If no boolean is provided, the value is assumed to be `true`.                                                                                                                                                  |
 /// | `#[sub_commands(commands)]`                                                  | The sub or children commands of this command. They are executed in the form: `this-command sub-command`. | `commands` is a comma separated list of identifiers referencing functions marked by the `#[command]` macro.                                                                                                                      |
+/// | `#[deprecated_command]` </br> `#[deprecated_command(notice)]` </br> `#[deprecated_command = notice]` | Marks the command as deprecated. It keeps working, but the help shows the notice and the framework logs a warning whenever it is invoked. | `notice` is a string, such as `"use bar instead"`. If no notice is provided, a generic one is used.                                                                                                |
+/// | `#[metrics(name = label, tags = [tags])]`                                   | Labels under which the command is reported to a metrics sink. Purely informative; the framework only exposes them through `CommandOptions::metrics`. | `label` is a non-empty string. `tags` is an optional, comma separated list of non-empty strings; repeated tags are kept once.                                                                                  |
+/// | `#[requires_data(Key)]` </br> `#[requires_data(binding: Key)]`            | A value of the context's data the command needs. It is cloned out of `ctx.data` before the command's body runs and bound to `binding`, or to the snake cased name of `Key` (`MyConfig` binds `my_config`). If it is absent, the command returns an error without running its body. May be given multiple times. | `Key` is a path to a type implementing `TypeMapKey`, whose `Value` implements `Clone`, such as an `Arc`. `binding` is an identifier, bound at most once. |
+/// | `#[on_error(handler)]`                                                       | A function called if the command returns an error, before the framework's `after` hook.                  | `handler` is an identifier referencing a function marked by the `#[hook]` macro, taking the context, the message and a reference to the error.                                                                                 |
+/// | `#[unknown_sub(handler)]`                                                    | A function called instead of the command if the word following it is not one of its sub commands. The command itself then only runs without arguments. Requires `sub_commands`. | `handler` is an identifier referencing a function marked by the `#[hook]` macro, taking the context, the message and the unknown word, and returning a `CommandResult`.</br> Unlike a group's `default_command`, which runs when none of the group's commands match, this only applies once this command has matched. |
+///
+/// Checks are evaluated in two phases: first the `priority_checks`, then the checks of the
+/// command's group followed by its own `checks`. Within each list, checks run in the order they
+/// are listed, and evaluation stops at the first failing check. The group's checks are skipped
+/// if the command sets `#[inherit_group_checks(false)]`.
 ///
 /// Documentation comments (`///`) applied onto the function are interpreted as sugar for the
 /// `#[description]` option. When more than one application of the option is performed,
 /// the text is delimited by newlines. This mimics the behaviour of regular doc-comments,
 /// which are sugar for the `#[doc = "..."]` attribute.
 ///
+/// An option may be written as `#[name = value]` or `#[name(value)]`, but not both ways on the
+/// same command. Documentation comments may accompany either form of `#[description]`.
+///
+/// With the `strict` feature of this crate enabled, a command listed in the help that has
+/// neither doc comments nor a `#[description]` fails to compile.
+///
+/// Regular Rust attributes, such as `#[cfg]`, `#[allow]`, `#[inline]`, `#[track_caller]` or tool attributes like
+/// `#[rustfmt::skip]`, are not treated as options and are kept on the generated function.
+/// `#[cfg]` and lint attributes are applied to the generated statics as well.
+///
 /// # Notes
 /// The name of the command is parsed from the applied function,
 /// or may be specified inside the `#[command]` attribute, a lá `#[command("foobar")]`.
 ///
+/// The function returns either a `CommandResult` or a `Result<(), E>`, where `E` is any error
+/// that converts into a `CommandError`, such as `serenity::Error`. In the latter case, `?` works
+/// with `E` directly, and the error is boxed into a `CommandError` once the command returns:
+/// ```rust,ignore
+/// #[command]
+/// async fn say(ctx: &Context, msg: &Message, args: Args) -> Result<(), serenity::Error> {
+///     msg.channel_id.say(&ctx.http, args.rest()).await?;
+///
+///     Ok(())
+/// }
+/// ```
+///
 /// This macro attribute generates static instances of `Command` and `CommandOptions`,
 /// conserving the provided options.
 ///
@@ -98,150 +152,752 @@ macro_rules! match_options {
 /// pub static FOO_COMMAND_OPTIONS: CommandOptions = CommandOptions { ... };
 /// pub static FOO_COMMAND: Command = Command { options: FOO_COMMAND_OPTIONS, ... };
 /// ```
+///
+/// Alongside these, a `pub fn foo_command_names() -> &'static [&'static str]` is generated,
+/// returning the command's name followed by its aliases. It is the same slice as
+/// `FOO_COMMAND_OPTIONS.names`, but does not depend on the layout of `CommandOptions`.
+///
+/// ## Arguments
+///
+/// Following the name, the `#[command]` attribute accepts arguments that change what is generated:
+///
+/// | Syntax        | Description                                                                                                                                                                                                                                  |
+/// | ------------- | -------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------- |
+/// | `fast_lookup` | Also generate `FOO_COMMAND_LOOKUP`, a `&[(&str, &Command)]` pairing the command's name and every alias with the command. The entries are deduplicated and sorted by name, so the slice can be searched with `binary_search_by_key`. |
+/// | `name_matcher` | Also generate `pub const fn foo_matches(name: &str) -> bool`, telling whether `name` is the command's name or one of its aliases. The comparison is exact, byte for byte, whatever the framework's case sensitivity. Requires Rust 1.46. |
+/// | `test`        | Also generate a `#[cfg(test)]` unit test, `foo_command_wellformed`, asserting that the command has at least one name, that none of its names or delimiters are empty, that `min_args` does not exceed `max_args`, and that no two sub commands share a name or alias. |
+/// | `preset = p`  | Apply the options of a preset declared with [`command_preset!`]. Options given on the command itself take precedence over the preset's. The preset is a `macro_rules!` macro, so it must be declared textually before the command (or in a `#[macro_use]` module declared before it). The command is then expanded again through `serenity::framework::standard::macros::command`, so the `serenity` crate must be reachable under that name, not renamed. |
+/// | `as_const`    | Generate `FOO_COMMAND` and `FOO_COMMAND_OPTIONS` as `const` items instead of `static`s, so that they can be used in other constants. Checks and sub commands are still referred to through their statics. |
+/// | `names(n, ...)` | Give the command several primary names instead of one. The first is used for `FOO_COMMAND` as usual; every further name `bar` generates its own `BAR_COMMAND` and `BAR_COMMAND_OPTIONS`, sharing the function and options. Cannot be combined with a name given before the arguments. |
+/// | `expose_name` | Declare `const __COMMAND_NAME: &str` in the function's body, holding the command's name. With `names`, this is the first name, as the function is shared. |
+/// | `options_from = f` | Use the `CommandOptions` returned by `f`, a `const fn() -> CommandOptions`, instead of building them from option attributes. Cannot be combined with any option attribute, doc comments included, nor with `names`, `fast_lookup` or `name_matcher`. |
+/// | `introspect`  | Also generate `FooCommandMarker`, a unit struct implementing `CommandInfo`, which exposes the command, its names, description and argument bounds through associated functions. The type is named in Pascal case, whatever the `static_case`. |
+/// | `inline_options` | Don't generate `FOO_COMMAND_OPTIONS`; the options are written directly into `FOO_COMMAND`, and are reachable through its `options` field. |
+/// | `static_case = c` | Case the generated statics' names with `c`: `"screaming"` (`FOO_COMMAND`, the default), `"pascal"` (`FooCommand`) or `"snake"` (`foo_command`). Sub commands are referred to with the same case. |
+/// | `fn_static = s` | Derive the generated statics' names from the string `s` instead of the function's name, e.g. `CUSTOM_COMMAND` for `"custom"`. The command's name and the function are unchanged. Groups and parent commands must then refer to the command as `custom`. |
+/// | `feature = f` | Compile the command, its function and its statics only if the crate feature `f` is enabled, and record `f` in `CommandOptions::required_feature`. Groups and parent commands listing the command must be gated on the same feature, e.g. with `#[cfg(feature = "music")] play` in a group's `commands`. |
+///
+/// [`command_preset!`]: macro.command_preset.html
 #[proc_macro_attribute]
 pub fn command(attr: TokenStream, input: TokenStream) -> TokenStream {
+    let item = proc_macro2::TokenStream::from(input.clone());
     let mut fun = parse_macro_input!(input as CommandFun);
 
-    let _name = if !attr.is_empty() {
-        parse_macro_input!(attr as Lit).to_str()
-    } else {
-        fun.name.to_string()
+    let AttributeArgs { name, values } = parse_macro_input!(attr as AttributeArgs);
+
+    let mut args = CommandArgs::default();
+    let mut forwarded = name.iter().map(|n| quote!(#n)).collect::<Vec<_>>();
+
+    for values in values {
+        let span = values.span;
+
+        let name = values.name.to_string();
+        let name = &name[..];
+
+        if name == "preset" {
+            args.preset = Some(propagate_err!(attributes::parse::<Preset>(values)).0);
+
+            continue;
+        }
+
+        forwarded.push(quote!(#values));
+
+        match_options!(name, values, args, span => [
+            fast_lookup;
+            name_matcher;
+            test;
+            names;
+            as_const;
+            static_case;
+            expose_name;
+            options_from;
+            inline_options;
+            introspect;
+            fn_static;
+            feature
+        ]);
+    }
+
+    // Let the preset's macro insert its options ahead of the command's own,
+    // then expand the command again without the preset.
+    if let Some(preset) = args.preset {
+        return (quote! {
+            #preset! {
+                #[serenity::framework::standard::macros::command(#(#forwarded),*)]
+                #item
+            }
+        })
+        .into();
+    }
+
+    if let (Some(lit), false) = (&name, args.names.is_empty()) {
+        return Error::new(lit.span(), "the command's name and `names` cannot be given together")
+            .to_compile_error()
+            .into();
+    }
+
+    let static_base = propagate_err!(args.static_base(&fun.name));
+    let required_feature = propagate_err!(args.feature_gate());
+
+    if let Some((_, gate)) = &required_feature {
+        fun.cooked.insert(0, gate.clone());
+    }
+
+    let required_feature = AsOption(required_feature.map(|(feature, _)| feature));
+
+    let mut names = args.names.drain(..);
+
+    let _name = match name {
+        Some(lit) => lit.to_str(),
+        None => match names.next() {
+            Some(first) => first,
+            None => fun.name.unraw().to_string(),
+        },
     };
 
+    // Every further name gets its own statics, pointing to the same function.
+    let extra_names = names.collect::<Vec<_>>();
+
+    let expose_name = args.name_constant(&_name);
+    let case = args.static_case;
+    let mut static_names = vec![name_with_case(&static_base, COMMAND, case)];
+
+    for extra in &extra_names {
+        let ident = name_with_case(extra, COMMAND, case);
+
+        if static_names.contains(&ident) {
+            return Error::new(
+                Span::call_site(),
+                format_args!("the name `{}` would generate `{}` twice", extra, ident),
+            )
+            .to_compile_error()
+            .into();
+        }
+
+        static_names.push(ident);
+    }
+
+    // The options' builder provides every option, leaving none for attributes to set.
+    let options_from = args.options_from.0;
+    let inline_options = args.inline_options;
+
+    if options_from.is_some() {
+        if let Some(attribute) = fun.attributes.first() {
+            return Error::new(
+                attribute.span(),
+                "`options_from` provides all of the command's options and cannot be combined with option attributes, including doc comments",
+            )
+            .to_compile_error()
+            .into();
+        }
+
+        if !extra_names.is_empty() || args.fast_lookup || args.name_matcher {
+            return Error::new(
+                Span::call_site(),
+                "`options_from` cannot be combined with `names`, `fast_lookup` or `name_matcher`, as the command's names are only known at runtime",
+            )
+            .to_compile_error()
+            .into();
+        }
+    }
+
     let mut options = Options::new();
+    let mut forms = OptionForms::default();
+
+    for (attribute, &doc_comment) in fun.attributes.iter().zip(&fun.doc_comments) {
+        if attribute.path.is_ident("description") {
+            if let Some(part) = propagate_err!(parse_description_source(attribute)) {
+                options.description.push(part);
+
+                continue;
+            }
+        }
+
+        if attribute.path.is_ident("localized_names") {
+            options.localized_names = propagate_err!(parse_localized(attribute, "name"));
+
+            continue;
+        }
+
+        if attribute.path.is_ident("localized_usage") {
+            options.localized_usages = propagate_err!(parse_localized(attribute, "usage"));
+
+            continue;
+        }
+
+        if attribute.path.is_ident("metrics") {
+            options.metrics = AsOption(Some(propagate_err!(parse_metrics(attribute))));
+
+            continue;
+        }
+
+        if attribute.path.is_ident("requires_data") {
+            let data = propagate_err!(attribute.parse_args::<RequiredData>());
+
+            if options.required_data.iter().any(|d| d.binding == data.binding) {
+                return Error::new(
+                    attribute.span(),
+                    format_args!("`{}` is bound by more than one `requires_data`", data.binding),
+                )
+                .to_compile_error()
+                .into();
+            }
+
+            options.required_data.push(data);
+
+            continue;
+        }
+
+        if attribute.path.is_ident("help_available") {
+            if let Some(reason) = propagate_err!(parse_help_unavailable_reason(attribute)) {
+                options.help_available = false;
+                options.help_unavailable_reason = AsOption(Some(reason));
+
+                continue;
+            }
+        }
+
+        if attribute.path.is_ident("example") {
+            if let Some(example) = propagate_err!(parse_keyed_example(attribute)) {
+                options.examples.push(example);
+
+                continue;
+            }
+        }
 
-    for attribute in &fun.attributes {
         let span = attribute.span();
         let values = propagate_err!(parse_values(attribute));
 
         let name = values.name.to_string();
         let name = &name[..];
 
+        // Documentation comments are sugar for `#[description = ...]`, and may accompany either form.
+        if !doc_comment {
+            propagate_err!(forms.insert(name, values.kind, span));
+        }
+
         match name {
             "num_args" => {
-                let args = propagate_err!(u16::parse(values));
+                let args = propagate_err!(ArgsBound::parse(values));
 
-                options.min_args = AsOption(Some(args));
+                options.min_args = AsOption(Some(args.clone()));
                 options.max_args = AsOption(Some(args));
             }
+            "checks_ordered" => {
+                if !propagate_err!(attributes::parse::<bool>(values)) {
+                    return Error::new(span, "checks always run in the order they are listed")
+                        .to_compile_error()
+                        .into();
+                }
+            }
             "example" => {
-                options
-                    .examples
-                    .push(propagate_err!(attributes::parse(values)));
+                options.examples.push(Example {
+                    input: propagate_err!(attributes::parse(values)),
+                    ..Example::default()
+                });
             }
             "description" => {
                 let arg: String = propagate_err!(attributes::parse(values));
 
-                if let Some(desc) = &mut options.description.0 {
-                    use std::fmt::Write;
-
-                    let _ = write!(desc, "\n{}", arg.trim_matches(' '));
-                } else {
-                    options.description = AsOption(Some(arg));
-                }
+                options.description.push(DescriptionPart::Text(arg));
+            }
+            "deprecated_command" => {
+                options.deprecate(propagate_err!(attributes::parse(values)));
             }
             _ => {
                 match_options!(name, values, options, span => [
                     checks;
+                    priority_checks;
+                    inherit_group_checks;
                     bucket;
+                    cooldown_bypass_roles;
+                    cooldown_bypass_role_ids;
                     aliases;
+                    prefixes;
+                    category;
                     delimiters;
                     usage;
                     min_args;
                     max_args;
+                    base_min_args;
+                    min_membership_seconds;
+                    timeout;
                     required_permissions;
                     allowed_roles;
+                    guilds;
+                    allowed_users;
+                    slash_compatible;
                     help_available;
                     only_in;
+                    delete_invocation;
+                    typing;
+                    requires_voice;
+                    requires_age_gated_guild;
                     owners_only;
                     owner_privilege;
-                    sub_commands
+                    sub_commands;
+                    on_error;
+                    unknown_sub;
+                    num_delimiters;
+                    delimiter_escape;
+                    quotes
                 ]);
             }
         }
     }
 
+    if options.slash_compatible {
+        let names = std::iter::once(&_name)
+            .chain(&extra_names)
+            .chain(&options.aliases)
+            .chain(options.localized_names.iter().map(|(_, name)| name));
+
+        for name in names {
+            if !is_slash_name(name) {
+                let attribute = fun
+                    .attributes
+                    .iter()
+                    .find(|a| a.path.is_ident("slash_compatible"))
+                    .unwrap();
+
+                return Error::new(
+                    attribute.span(),
+                    format_args!(
+                        "`{}` is not a valid slash command name; names must be 1 to 32 lowercase letters, digits, `-` or `_`",
+                        name
+                    ),
+                )
+                .to_compile_error()
+                .into();
+            }
+        }
+    }
+
+    if cfg!(feature = "strict") && options_from.is_none() {
+        propagate_err!(options.require_description(&fun.name));
+    }
+
+    propagate_err!(options.check_unknown_sub());
+
+    propagate_err!(options.reject_self_sub_command(&fun.name));
+
+    propagate_err!(options.check_prefixes(&fun.attributes));
+
+    if options.bucket.0.is_none()
+        && !(options.cooldown_bypass_roles.is_empty() && options.cooldown_bypass_role_ids.is_empty())
+    {
+        let attribute = fun
+            .attributes
+            .iter()
+            .find(|a| a.path.is_ident("cooldown_bypass_roles") || a.path.is_ident("cooldown_bypass_role_ids"))
+            .unwrap();
+
+        return Error::new(
+            attribute.span(),
+            "bypassing the cooldown requires the command to have a `bucket`",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    propagate_err!(options.check_num_delimiters(&fun.attributes));
+
+    let (usage_parts, usage_warning) = options.usage.0.as_deref().map(parse_usage).unwrap_or_default();
+    let usage_warning = usage_warning.map(|message| {
+        let span = fun
+            .attributes
+            .iter()
+            .find(|a| a.path.is_ident("usage"))
+            .map_or_else(Span::call_site, |a| a.span());
+
+        warning(&message, span)
+    });
+
+    // Keep only the first occurrence of repeated entries, so that `names` lists every name once.
+    options.aliases.retain(|alias| *alias != _name);
+    dedup(&mut options.aliases);
+    dedup(&mut options.allowed_roles);
+    dedup(&mut options.cooldown_bypass_roles);
+    dedup(&mut options.cooldown_bypass_role_ids);
+    dedup(&mut options.checks.0);
+
+    if let Some(quotes) = &mut options.quotes.0 {
+        dedup(quotes);
+    }
+
     let Options {
         checks,
+        priority_checks,
+        inherit_group_checks,
         bucket,
+        cooldown_bypass_roles,
+        cooldown_bypass_role_ids,
         aliases,
+        prefixes,
         description,
+        category,
         delimiters,
         usage,
         examples,
+        localized_names,
+        localized_usages,
+        required_data,
         min_args,
         max_args,
+        base_min_args,
+        min_membership_seconds,
+        timeout,
         allowed_roles,
+        guilds,
+        allowed_users,
+        slash_compatible,
         required_permissions,
         help_available,
+        help_unavailable_reason,
         only_in,
+        delete_invocation,
+        typing,
+        requires_voice,
+        requires_age_gated_guild,
         owners_only,
         owner_privilege,
         sub_commands,
+        on_error,
+        unknown_sub,
+        num_delimiters,
+        delimiter_escape,
+        quotes,
+        deprecation_notice,
+        metrics,
     } = options;
 
     propagate_err!(create_declaration_validations(&mut fun, DeclarFor::Command));
+    create_command_return_validation(&mut fun);
 
-    let res = parse_quote!(serenity::framework::standard::CommandResult);
-    create_return_type_validation(&mut fun, res);
+    let ctx = fun.args[0].name.clone();
+    fun.body.splice(0..0, required_data.iter().map(|data| data.fetch(&ctx)));
 
     let visibility = fun.visibility;
     let name = fun.name.clone();
-    let options = name.with_suffix(COMMAND_OPTIONS);
+    let options = name_with_case(&static_base, COMMAND_OPTIONS, case);
     let sub_commands = sub_commands
         .into_iter()
-        .map(|i| i.with_suffix(COMMAND))
+        .map(|i| i.with_cased_suffix(COMMAND, case))
         .collect::<Vec<_>>();
+    let localized_names = localized_names
+        .iter()
+        .map(|(locale, name)| quote!((#locale, #name)))
+        .collect::<Vec<_>>();
+    let localized_usages = localized_usages
+        .iter()
+        .map(|(locale, usage)| quote!((#locale, #usage)))
+        .collect::<Vec<_>>();
+    let example_permissions = examples.iter().map(|example| &example.requires).collect::<Vec<_>>();
+    let quotes = quotes.0.unwrap_or_else(|| vec!['"']);
+    let error_handler = on_error.map(|i| quote!(serenity::framework::standard::CommandErrorHandler(#i)));
+    let unknown_sub = unknown_sub.map(|i| quote!(serenity::framework::standard::UnknownSubHandler(#i)));
     let body = fun.body;
     let ret = fun.ret;
 
-    let n = name.with_suffix(COMMAND);
+    let n = name_with_case(&static_base, COMMAND, case);
+    let names_fn = format_ident!("{}_command_names", name.unraw());
 
-    let cooked = fun.cooked.clone();
+    let cooked = fun
+        .cooked
+        .iter()
+        .filter(|a| is_static_compatible(a))
+        .cloned()
+        .collect::<Vec<_>>();
     let cooked2 = cooked.clone();
+    let cooked3 = cooked.clone();
+    let fun_cooked = fun.cooked;
 
     let options_path = quote!(serenity::framework::standard::CommandOptions);
     let command_path = quote!(serenity::framework::standard::Command);
 
+    let item_kind = if args.as_const { quote!(const) } else { quote!(static) };
+    let allow_case = case.lint_allowance();
+
+    let lookup = if args.fast_lookup {
+        let names = std::iter::once(&_name).chain(&aliases).map(String::as_str).collect::<Vec<_>>();
+        let lookup = lookup_table(&name_with_case(&static_base, COMMAND_LOOKUP, case), &n, &names);
+
+        quote! {
+            #(#cooked)*
+            #allow_case
+            #lookup
+        }
+    } else {
+        quote!()
+    };
+
+    let matcher = if args.name_matcher {
+        let names = std::iter::once(&_name).chain(&aliases).map(String::as_str).collect::<Vec<_>>();
+        let matcher = name_matcher(&format_ident!("{}_matches", name.unraw()), &names);
+
+        quote! {
+            #(#cooked)*
+            #matcher
+        }
+    } else {
+        quote!()
+    };
+
+    let test = if args.test {
+        let test = format_ident!("{}_command_wellformed", name.unraw());
+
+        quote! {
+            #(#cooked)*
+            #[cfg(test)]
+            #[test]
+            fn #test() {
+                let options = #n.options;
+
+                assert!(!options.names.is_empty(), "the command has no names");
+                assert!(options.names.iter().all(|n| !n.is_empty()), "the command has an empty name");
+                assert!(options.delimiters.iter().all(|d| !d.is_empty()), "the command has an empty delimiter");
+
+                if let (Some(min), Some(max)) = (options.min_args, options.max_args) {
+                    assert!(min <= max, "`min_args` ({}) exceeds `max_args` ({})", min, max);
+                }
+
+                let mut sub_names = std::collections::HashSet::new();
+
+                for sub in options.sub_commands {
+                    for name in sub.options.names {
+                        assert!(sub_names.insert(*name), "the name `{}` is shared by several sub commands", name);
+                    }
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    let introspect = if args.introspect {
+        // Types are named in Pascal case regardless of the statics' case.
+        let marker = name_with_case(&static_base, COMMAND_MARKER, StaticCase::Pascal);
+        let info_path = quote!(serenity::framework::standard::CommandInfo);
+
+        quote! {
+            #(#cooked)*
+            #[doc = "Marker type of the command, implementing `CommandInfo`."]
+            #[derive(Debug, Clone, Copy)]
+            pub struct #marker;
+
+            #(#cooked)*
+            impl #info_path for #marker {
+                fn command() -> &'static #command_path {
+                    &#n
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+
+
     populate_fut_lifetimes_on_refs(&mut fun.args);
     let args = fun.args;
 
-    (quote! {
-        #(#cooked)*
-        pub static #options: #options_path = #options_path {
+    let options_literal = |primary: &str| quote! {
+        #options_path {
             checks: #checks,
+            priority_checks: #priority_checks,
+            inherit_group_checks: #inherit_group_checks,
             bucket: #bucket,
-            names: &[#_name, #(#aliases),*],
+            cooldown_bypass_roles: &[#(#cooldown_bypass_roles),*],
+            cooldown_bypass_role_ids: &[#(#cooldown_bypass_role_ids),*],
+            names: &[#primary, #(#aliases),*],
+            prefixes: &[#(#prefixes),*],
             desc: #description,
+            category: #category,
             delimiters: &[#(#delimiters),*],
             usage: #usage,
+            usage_parts: &[#(#usage_parts),*],
             examples: &[#(#examples),*],
+            example_permissions: &[#(#example_permissions),*],
+            localized_names: &[#(#localized_names),*],
+            localized_usages: &[#(#localized_usages),*],
             min_args: #min_args,
             max_args: #max_args,
+            base_min_args: #base_min_args,
+            min_membership_seconds: #min_membership_seconds,
+            timeout: #timeout,
             allowed_roles: &[#(#allowed_roles),*],
+            allowed_guilds: &[#(#guilds),*],
+            allowed_users: &[#(#allowed_users),*],
+            slash_compatible: #slash_compatible,
             required_permissions: #required_permissions,
             help_available: #help_available,
+            help_unavailable_reason: #help_unavailable_reason,
             only_in: #only_in,
+            delete_invocation: #delete_invocation,
+            typing: #typing,
+            requires_voice: #requires_voice,
+            requires_age_gated_guild: #requires_age_gated_guild,
             owners_only: #owners_only,
             owner_privilege: #owner_privilege,
             sub_commands: &[#(&#sub_commands),*],
+            error_handler: #error_handler,
+            unknown_sub: #unknown_sub,
+            num_delimiters: #num_delimiters,
+            delimiter_escape: #delimiter_escape,
+            quotes: &[#(#quotes),*],
+            deprecation_notice: #deprecation_notice,
+            metrics: #metrics,
+            required_feature: #required_feature,
+        }
+    };
+
+    // Returns the options' item, if they get one, and the expression the command refers to them with.
+    let options_for = |ident: &Ident, literal: proc_macro2::TokenStream| {
+        if inline_options {
+            return (quote!(), quote!(&#literal));
+        }
+
+        let item = quote! {
+            #(#cooked)*
+            #allow_case
+            pub #item_kind #ident: #options_path = #literal;
         };
 
+        (item, quote!(&#ident))
+    };
+
+    let (main_options, main_options_ref) = match options_from {
+        Some(builder) => options_for(&options, quote!(#builder())),
+        None => options_for(&options, options_literal(&_name)),
+    };
+
+    let extra_statics = extra_names.iter().map(|extra| {
+        let extra_options = name_with_case(extra, COMMAND_OPTIONS, case);
+        let extra_command = name_with_case(extra, COMMAND, case);
+        let (options_static, options_ref) = options_for(&extra_options, options_literal(extra));
+
+        quote! {
+            #options_static
+
+            #(#cooked2)*
+            #allow_case
+            pub #item_kind #extra_command: #command_path = #command_path {
+                fun: #name,
+                options: #options_ref,
+            };
+        }
+    }).collect::<Vec<_>>();
+
+    (quote! {
+        #main_options
+
         #(#cooked2)*
-        pub static #n: #command_path = #command_path {
+        #allow_case
+        pub #item_kind #n: #command_path = #command_path {
             fun: #name,
-            options: &#options,
+            options: #main_options_ref,
         };
 
+        #(#extra_statics)*
+
+        #usage_warning
+
+        #lookup
+
+        #matcher
+
+        #test
+
+        #introspect
+
+        #(#cooked3)*
+        #[allow(dead_code)]
+        pub fn #names_fn() -> &'static [&'static str] {
+            #n.options.names
+        }
+
+        #(#fun_cooked)*
         #visibility fn #name<'fut> (#(#args),*) -> ::serenity::futures::future::BoxFuture<'fut, #ret> {
             use ::serenity::futures::future::FutureExt;
 
-            async move { #(#body)* }.boxed()
+            async move {
+                #expose_name
+
+                #(#body)*
+            }.boxed()
         }
     })
     .into()
 }
 
+/// Declares a reusable set of options for the [`command`] macro.
+///
+/// The first argument is the preset's name, followed by a braced list of `option: value` pairs.
+/// Options are the same as those of [`command`]; a value is either a literal,
+/// an identifier, or a bracketed, comma separated list of either.
+///
+/// ```rust,ignore
+/// command_preset!("moderator", {
+///     only_in: guilds,
+///     required_permissions: [BAN_MEMBERS, KICK_MEMBERS],
+///     help_available: false,
+/// });
+///
+/// #[command(preset = moderator)]
+/// #[help_available]
+/// async fn ban(ctx: &Context, msg: &Message) -> CommandResult {
+///     // ...
+/// }
+/// ```
+///
+/// Applying the preset with `#[command(preset = moderator)]` inserts its options before the
+/// command's own, so an option given on the command overrides the preset's value.
+///
+/// The preset expands to a `macro_rules!` macro of the same name. As such, it must be declared
+/// textually before the commands using it, and follows the usual scoping rules of such macros.
+/// The preset's macro invokes `serenity::framework::standard::macros::command` by that path,
+/// so presets cannot be used when the `serenity` dependency is renamed.
+///
+/// [`command`]: attr.command.html
+#[proc_macro]
+pub fn command_preset(input: TokenStream) -> TokenStream {
+    let CommandPreset { name, attributes } = parse_macro_input!(input as CommandPreset);
+
+    (quote! {
+        macro_rules! #name {
+            (#[$($command:tt)*] $($item:tt)*) => {
+                #[$($command)*]
+                #(#attributes)*
+                $($item)*
+            };
+        }
+    })
+    .into()
+}
+
+/// Asserts that a command's options have the expected values.
+///
+/// The first argument is the path of the options' static, followed by `field = value` pairs,
+/// where `field` is a field of `CommandOptions`. Fields holding an `Option` are compared to
+/// `Some(value)`, unless the value is written as `Some(...)` or `None`.
+///
+/// The values are checked at compile time, so the macro can be used wherever an item can,
+/// and a wrong value fails the build at the field that does not match:
+///
+/// ```rust,ignore
+/// assert_command_options!(BAN_COMMAND_OPTIONS, min_args = 1, owners_only = true, bucket = "mod");
+/// ```
+///
+/// Each value is used as a pattern, so it must be a literal, `Some`/`None` of one, or a
+/// constant or enum variant such as `OnlyIn::Guild`. This requires Rust 1.46, and before
+/// Rust 1.83 the command must be declared with `#[command(as_const)]`, since statics could
+/// not be read at compile time.
+#[proc_macro]
+pub fn assert_command_options(input: TokenStream) -> TokenStream {
+    let assertions = parse_macro_input!(input as OptionAssertions);
+
+    assertions.into_token_stream().into()
+}
+
 /// A brother macro to [`command`], but for the help command.
 /// An interface for simple browsing of all the available commands the bot provides,
 /// and reading through specific information regarding a command.
@@ -263,21 +919,46 @@ pub fn command(attr: TokenStream, input: TokenStream) -> TokenStream {
 /// | `#[aliases_label(s)]` </br> `#[aliases_label= s]`                                                                                             | Label for a command's aliases.                                                                                                                                                                                                                   | `s` is a string                                                                                            |
 /// | `#[guild_only_text(s)]` </br> `#[guild_only_text = s]`                                                                                        | When a command is specific to guilds only.                                                                                                                                                                                                       | `s` is a string                                                                                            |
 /// | `#[checks_label(s)]` </br> `#[checks_label = s]`                                                                                              | The header text when showing checks in the help command.                                                                                                                                                                                         | `s` is a string                                                                                            |
+/// | `#[deprecated_label(s)]` </br> `#[deprecated_label = s]`                                                                                      | Label for a deprecated command's notice.                                                                                                                                                                                                         | `s` is a string                                                                                            |
 /// | `#[dm_only_text(s)]` </br> `#[dm_only_text = s]`                                                                                              | When a command is specific to dms only.                                                                                                                                                                                                          | `s` is a string                                                                                            |
 /// | `#[dm_and_guild_text(s)]` </br> `#[dm_and_guild_text = s]`                                                                                    | When a command is usable in both guilds and dms.                                                                                                                                                                                                 | `s` is a string                                                                                            |
 /// | `#[available_text(s)]` </br> `#[available_text = s]`                                                                                          | When a command is available.                                                                                                                                                                                                                     | `s` is a string                                                                                            |
+/// | `#[show_availability]` </br> `#[show_availability(b)]`                                                                                        | Whether to show in which channels a command is available. Defaults to `true`.                                                                                                                                                                    | `b` is a boolean. If no boolean is provided, the value is assumed to be `true`.                            |
+/// | `#[show_permissions]` </br> `#[show_permissions(b)]`                                                                                          | Whether to show the permissions a command requires. Defaults to `false`.                                                                                                                                                                         | `b` is a boolean. If no boolean is provided, the value is assumed to be `true`.                            |
+/// | `#[permissions_label(s)]` </br> `#[permissions_label = s]`                                                                                    | Label for a command's required permissions, shown with `show_permissions`.                                                                                                                                                                       | `s` is a string                                                                                            |
 /// | `#[command_not_found_text(s)]` </br> `#[command_not_found_text = s]`                                                                          | When a command wasn't found.                                                                                                                                                                                                                     | `s` is a string                                                                                            |
-/// | `#[individual_command_tip(s)]` </br> `#[individual_command_tip = s]`                                                                          | How the user should access a command's details.                                                                                                                                                                                                  | `s` is a string                                                                                            |
+/// | `#[individual_command_tip(s)]` </br> `#[individual_command_tip = s]`                                                                          | How the user should access a command's details.                                                                                                                                                                                                  | `s` is a string. May contain the `{prefix}` and `{command}` tokens, replaced with the prefix and name the help command was invoked with. |
 /// | `#[strikethrough_commands_tip_in_dm(s)]` </br>  `#[strikethrough_commands_tip_in_dm = s]`                                                     | Reasoning behind strikethrough-commands.</br> *Only used in dms.*                                                                                                                                                                                | `s` is a string. If not provided, default text will be used instead.                                       |
 /// | `#[strikethrough_commands_tip_in_guild(s)]` </br> `#[strikethrough_commands_tip_in_guild = s]`                                                | Reasoning behind strikethrough-commands.</br> *Only used in guilds.*                                                                                                                                                                             | `s` is a string. If not provided, default text will be used instead.                                       |
 /// | `#[group_prefix(s)]` </br> `#[group_prefix = s]`                                                                                              | For introducing a group's prefix                                                                                                                                                                                                                 | `s` is a string                                                                                            |
-/// | `#[lacking_role(s)]` </br> `#[lacking_role = s]`                                                                                              | If a user lacks required roles, this will treat how commands will be displayed.                                                                                                                                                                  | `s` is a string. Accepts `strike` (strikethroughs), `hide` (will not be listed) or `nothing`(leave be).    |
-/// | `#[lacking_ownership(s)]` </br> `#[lacking_ownership = s]`                                                                                    | If a user lacks ownership, this will treat how these commands will be displayed.                                                                                                                                                                 | `s` is a string. Accepts `strike` (strikethroughs), `hide` (will not be listed) or `nothing`(leave be).    |
-/// | `#[lacking_permissions(s)]` </br> `#[lacking_permissions = s]`                                                                                | If a user lacks permissions, this will treat how commands will be displayed.                                                                                                                                                                     | `s` is a string. Accepts `strike` (strikethroughs), `hide` (will not be listed) or `nothing`(leave be).    |
-/// | `#[embed_error_colour(n)]`                                                                                                                    | Colour that the help-embed will use upon an error.                                                                                                                                                                                               | `n` is a name to one of the provided constants of the `Colour` struct.                                     |
-/// | `#[embed_success_colour(n)]`                                                                                                                  | Colour that the help-embed will use normally.                                                                                                                                                                                                    | `n` is a name to one of the provided constants of the `Colour` struct.                                     |
+/// | `#[lacking_role(s)]` </br> `#[lacking_role = s]`                                                                                              | If a user lacks required roles, this will treat how commands will be displayed.                                                                                                                                                                  | `s` is a string. Accepts `strike` (strikethroughs), `hide` (will not be listed), `collapse` (counted in a single line of hidden commands) or `nothing`(leave be). |
+/// | `#[lacking_ownership(s)]` </br> `#[lacking_ownership = s]`                                                                                    | If a user lacks ownership, this will treat how these commands will be displayed.                                                                                                                                                                 | `s` is a string. Accepts `strike` (strikethroughs), `hide` (will not be listed), `collapse` (counted in a single line of hidden commands) or `nothing`(leave be). |
+/// | `#[lacking_permissions(s)]` </br> `#[lacking_permissions = s]`                                                                                | If a user lacks permissions, this will treat how commands will be displayed.                                                                                                                                                                     | `s` is a string. Accepts `strike` (strikethroughs), `hide` (will not be listed), `collapse` (counted in a single line of hidden commands) or `nothing`(leave be). |
+/// | `#[embed_error_colour(n)]`                                                                                                                    | Colour that the help-embed will use upon an error.                                                                                                                                                                                               | `n` is the name of one of the `Colour` struct's constants, such as `RED`, an integer such as `0xE74C3C`, or the path of a `u32` constant in scope, quoted if it has several segments. |
+/// | `#[embed_success_colour(n)]`                                                                                                                  | Colour that the help-embed will use normally.                                                                                                                                                                                                    | `n` is the name of one of the `Colour` struct's constants, such as `RED`, an integer such as `0xE74C3C`, or the path of a `u32` constant in scope, quoted if it has several segments. |
 /// | `#[max_levenshtein_distance(n)]`                                                                                                              | How much should the help command search for a similiar name.</br> Indicator for a nested guild. The prefix will be repeated based on what kind of level the item sits. A sub-group would be level two, a sub-sub-group would be level three.     | `n` is a 64-bit, unsigned integer.                                                                         |
 /// | `#[indention_prefix(s)]` </br> `#[indention_prefix = s]`                                                                                      | The prefix used to express how deeply nested a command or group is.                                                                                                                                                                              | `s` is a string                                                                                            |
+/// | `#[max_embed_fields(n)]`                                                                                                                      | How many groups the embedded help lists per message. Further groups are sent in additional messages. Defaults to `25`.                                                                                                                          | `n` is an unsigned integer, at most `25`.                                                                  |
+/// | `#[sort_groups_by(s)]` </br> `#[sort_groups_by = s]`                                                                                        | How the groups are ordered when listing all commands. Defaults to `declaration`, the order the groups are passed to the help in.                                                                                                                 | `s` is a string. Accepts `declaration`, `weight` (by each group's `order`, lowest first, ties keeping their declaration order) or `name` (alphabetically). |
+/// | `#[max_field_length(n)]`                                                                                                                      | How long the list of a group's commands may be in the embedded help. Longer lists are cut off. Defaults to `1024`.                                                                                                                               | `n` is an unsigned integer, at most `1024`.                                                                |
+/// | `#[footer_text(s)]` </br> `#[footer_text = s]`                                                                                                | Text shown at the bottom of the help's command listing and of a single command's details. Defaults to no footer.                                                                                                                               | `s` is a string. May contain the `{prefix}` and `{command}` tokens, like `individual_command_tip`.         |
+/// | `#[prefix_display(s)]` </br> `#[prefix_display = s]`                                                                                          | Text put in front of the invocations shown in usage and example lines, such as `"@Bot "` for bots invoked by mention. Defaults to nothing.                                                                                                     | `s` is a string. May contain the `{prefix}` and `{command}` tokens, like `individual_command_tip`.         |
+/// | `#[collapsed_commands_text(s)]` </br> `#[collapsed_commands_text = s]` </br> `#[collapsed_commands_text(s, p)]` | The line counting a group's commands collapsed by a `lacking_*` or `wrong_channel` behaviour. Defaults to `*{count} hidden command*` and `*{count} hidden commands*`.                                                                           | `s` and `p` are strings, the singular and plural texts. If only `s` is given, it is used for both. May contain the `{count}` token. |
+/// | `#[locale(s)]` </br> `#[locale = s]`                                                                                                          | The locale the help is shown in. Commands with a `localized_usage` for it show that usage. Defaults to nothing, showing each command's `usage`.                                                                                                   | `s` is a string, such as `en` or `pt_br`.                                                                  |
+/// | `#[paginate_after(n)]`                                                                                                                        | After how many characters the plain help is split into several messages, preferably at line breaks. Defaults to `0`, which disables pagination.                                                                                                  | `n` is an unsigned integer.                                                                                |
+///
+/// Each option may only be applied once.
+///
+/// As with [`command`], regular Rust attributes are kept on the generated function, and `#[cfg]`
+/// and lint attributes are applied to the generated `HELP_OPTIONS` and `HELP` statics as well.
+/// Options may be varied by feature with `#[cfg_attr]`, which the compiler resolves before the
+/// macro sees the options:
+/// ```rust,ignore
+/// #[help]
+/// #[cfg_attr(feature = "fr", suggestion_text = "Vouliez-vous dire `{}` ?")]
+/// #[cfg_attr(not(feature = "fr"), suggestion_text = "Did you mean `{}`?")]
+/// async fn my_help(...) -> CommandResult { ... }
+/// ```
 ///
 /// [`command`]: attr.command.html
 #[proc_macro_attribute]
@@ -301,6 +982,7 @@ pub fn help(attr: TokenStream, input: TokenStream) -> TokenStream {
     };
 
     let mut options = HelpOptions::default();
+    let mut seen = SeenOptions::default();
 
     for attribute in &fun.attributes {
         let span = attribute.span();
@@ -309,6 +991,8 @@ pub fn help(attr: TokenStream, input: TokenStream) -> TokenStream {
         let name = values.name.to_string();
         let name = &name[..];
 
+        propagate_err!(seen.insert(name, span));
+
         match_options!(name, values, options, span => [
             suggestion_text;
             no_help_available_text;
@@ -320,9 +1004,13 @@ pub fn help(attr: TokenStream, input: TokenStream) -> TokenStream {
             description_label;
             guild_only_text;
             checks_label;
+            deprecated_label;
             dm_only_text;
             dm_and_guild_text;
             available_text;
+            show_availability;
+            show_permissions;
+            permissions_label;
             command_not_found_text;
             individual_command_tip;
             group_prefix;
@@ -337,8 +1025,31 @@ pub fn help(attr: TokenStream, input: TokenStream) -> TokenStream {
             strikethrough_commands_tip_in_guild;
             sub_commands_label;
             max_levenshtein_distance;
-            indention_prefix
+            indention_prefix;
+            max_embed_fields;
+            sort_groups_by;
+            max_field_length;
+            paginate_after;
+            footer_text;
+            prefix_display;
+            collapsed_commands_text;
+            locale
         ]);
+
+        let (value, limit) = match name {
+            "max_embed_fields" => (options.max_embed_fields, EMBED_MAX_FIELDS),
+            "max_field_length" => (options.max_field_length, EMBED_MAX_FIELD_LENGTH),
+            _ => continue,
+        };
+
+        if value == 0 || value > limit {
+            return Error::new(
+                span,
+                format_args!("`{}` must be between 1 and {}, Discord's limit", name, limit),
+            )
+            .to_compile_error()
+            .into();
+        }
     }
 
     fn produce_strike_text(options: &HelpOptions, dm_or_guild: &str) -> Option<String> {
@@ -418,10 +1129,14 @@ pub fn help(attr: TokenStream, input: TokenStream) -> TokenStream {
         description_label,
         guild_only_text,
         checks_label,
+        deprecated_label,
         sub_commands_label,
         dm_only_text,
         dm_and_guild_text,
         available_text,
+        show_availability,
+        show_permissions,
+        permissions_label,
         command_not_found_text,
         individual_command_tip,
         group_prefix,
@@ -436,6 +1151,14 @@ pub fn help(attr: TokenStream, input: TokenStream) -> TokenStream {
         embed_success_colour,
         max_levenshtein_distance,
         indention_prefix,
+        max_embed_fields,
+        sort_groups_by,
+        max_field_length,
+        paginate_after,
+        footer_text,
+        prefix_display,
+        collapsed_commands_text,
+        locale,
     } = options;
 
     let strikethrough_commands_tip_in_dm = AsOption(strikethrough_commands_tip_in_dm);
@@ -451,8 +1174,14 @@ pub fn help(attr: TokenStream, input: TokenStream) -> TokenStream {
     let n = fun.name.to_uppercase();
     let nn = fun.name.clone();
 
-    let cooked = fun.cooked.clone();
+    let cooked = fun
+        .cooked
+        .iter()
+        .filter(|a| is_static_compatible(a))
+        .cloned()
+        .collect::<Vec<_>>();
     let cooked2 = cooked.clone();
+    let fun_cooked = fun.cooked;
 
     let options_path = quote!(serenity::framework::standard::HelpOptions);
     let command_path = quote!(serenity::framework::standard::HelpCommand);
@@ -476,10 +1205,14 @@ pub fn help(attr: TokenStream, input: TokenStream) -> TokenStream {
             description_label: #description_label,
             guild_only_text: #guild_only_text,
             checks_label: #checks_label,
+            deprecated_label: #deprecated_label,
             sub_commands_label: #sub_commands_label,
             dm_only_text: #dm_only_text,
             dm_and_guild_text: #dm_and_guild_text,
             available_text: #available_text,
+            show_availability: #show_availability,
+            show_permissions: #show_permissions,
+            permissions_label: #permissions_label,
             command_not_found_text: #command_not_found_text,
             individual_command_tip: #individual_command_tip,
             group_prefix: #group_prefix,
@@ -494,6 +1227,14 @@ pub fn help(attr: TokenStream, input: TokenStream) -> TokenStream {
             embed_success_colour: #embed_success_colour,
             max_levenshtein_distance: #max_levenshtein_distance,
             indention_prefix: #indention_prefix,
+            max_embed_fields: #max_embed_fields,
+            sort_groups_by: #sort_groups_by,
+            max_field_length: #max_field_length,
+            paginate_after: #paginate_after,
+            footer_text: #footer_text,
+            prefix_display: #prefix_display,
+            collapsed_commands_text: #collapsed_commands_text,
+            locale: #locale,
         };
 
         #(#cooked2)*
@@ -502,6 +1243,7 @@ pub fn help(attr: TokenStream, input: TokenStream) -> TokenStream {
             options: &#options,
         };
 
+        #(#fun_cooked)*
         pub fn #nn<'fut>(#(#args),*) -> ::serenity::futures::future::BoxFuture<'fut, #ret> {
             use ::serenity::futures::future::FutureExt;
 
@@ -559,25 +1301,54 @@ pub fn help(attr: TokenStream, input: TokenStream) -> TokenStream {
 /// struct Foo;
 /// ```
 ///
+/// The macro may also be applied onto a `mod`. Every function directly inside the module that is
+/// marked by `#[command]` (or a path ending in `command`, such as
+/// `#[serenity::framework::standard::macros::command]`) becomes one of the group's commands,
+/// with its `#[cfg]`s carried over. Functions in nested modules are not looked for.
+/// Commands listed with `#[commands]` are kept, ahead of the discovered ones.
+/// The module must have a body, and cannot be combined with `#[options]`.
+///
+/// ```rust,ignore
+/// #[group(prefix = "math")]
+/// mod math {
+///     use super::*;
+///
+///     #[command]
+///     async fn add(ctx: &Context, msg: &Message, args: Args) -> CommandResult { ... }
+///
+///     #[command]
+///     async fn multiply(ctx: &Context, msg: &Message, args: Args) -> CommandResult { ... }
+/// }
+/// ```
+///
+/// This generates `MATH_GROUP` and `MATH_GROUP_OPTIONS` next to the module, referring to
+/// `math::ADD_COMMAND` and `math::MULTIPLY_COMMAND`.
+///
 /// ## Options
 ///
-/// These appear after `#[group]` as a series of attributes:
+/// These appear after `#[group]` as a series of attributes, or inside it as arguments,
+/// such as `#[group(prefix = "foo", owners_only)]`:
 ///
 /// | Syntax                                               | Description                                                                        | Argument explanation                                                                                                                                                                 |
 /// |------------------------------------------------------|------------------------------------------------------------------------------------| -------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------|
-/// | `#[commands(commands)]`                              | Set of commands belonging to this group.                                           | `commands` is a comma separated list of identifiers referencing functions marked by the `#[command]` macro                                                                           |
+/// | `#[commands(commands)]`                              | Set of commands belonging to this group.                                           | `commands` is a comma separated list of identifiers referencing functions marked by the `#[command]` macro.</br> An identifier may be preceded by `#[cfg]`s, e.g. `#[commands(#[cfg(feature = "x")] foo, bar)]`, which gate the reference to match a gated command. |
 /// | `#[sub_groups(subs)]`                                | Set of sub groups belonging to this group.                                         | `subs` is a comma separated list of identifiers referencing structs marked by the `#[group]` macro                                                                                   |
 /// | `#[prefixes(prefs)]`                                 | Text that must appear   before an invocation of a command of this group may occur. | `prefs` is a comma separated list of strings                                                                                                                                         |
 /// | `#[prefix(pref)]`                                    | Assign just a single prefix.                                                       | `pref` is a string                                                                                                                                                                   |
 /// | `#[allowed_roles(roles)]`                            | Set of roles the user must possess                                                 | `roles` is a comma separated list of strings containing role names                                                                                                                   |
-/// | `#[only_in(ctx)]`                                    | Which environment the command can be executed in.                                  | `ctx` is a string with the accepted values `guild`/`guilds` and `dm`/ `dms` (Direct Message).                                                                                        |
+/// | `#[only_in(ctx)]`                                    | Which environment the group's commands can be executed in. Sub-groups and commands may override it with their own `only_in`. | `ctx` is a string with the accepted values `guild`/`guilds`, `dm`/ `dms` (Direct Message), `both` and `inherit`.                                                                                |
 /// | `#[owners_only]` </br> `#[owners_only(b)]`           | If this command is exclusive to owners.                                            | `b` is a boolean. If no boolean is provided, the value is assumed to be `true`.                                                                                                      |
 /// | `#[owner_privilege]` </br> `#[owner_privilege(b)]`   | If owners can bypass certain options.                                              | `b` is a boolean. If no boolean is provided, the value is assumed to be `true`.                                                                                                      |
 /// | `#[help_available]` </br> `#[help_available(b)]`     | If the group should be displayed in the help message.                              | `b` is a boolean. If no boolean is provided, the value is assumed to be `true`.                                                                                                      |
 /// | `#[checks(identifiers)]`                             | Preconditions that must met before the command's execution.                        | `identifiers` is a comma separated list of identifiers referencing functions marked by the `#[check]` macro                                                                          |
-/// | `#[required_permissions(perms)]`                     | Set of permissions the user must possess.                                          | `perms` is a comma separated list of permission names.</br> These can be found at [Discord's official documentation](https://discord.com/developers/docs/topics/permissions).     |
+/// | `#[required_permissions(perms)]`                     | Set of permissions the user must possess.                                          | `perms` is a comma separated list of permission names.</br> These can be found at [Discord's official documentation](https://discord.com/developers/docs/topics/permissions). The shorthands `ADMIN`, `KICK`, `BAN`, `MUTE`, `DEAFEN`, `MOVE` and `MANAGE_SERVER` are also accepted.     |
+/// | `#[order(n)]` </br> `#[order = n]`                   | The group's weight, used by a help sorting groups by weight. Lower weights are listed first. Defaults to `0`. | `n` is a 32-bit, signed integer.                                                                                                                                                      |
 /// | `#[default_command(cmd)]`                            | A command to execute if none of the group's prefixes are given.                    | `cmd` is an identifier referencing a function marked by the `#[command]` macro                                                                                                       |
-/// | `#[description(desc)]` </br> `#[description = desc]` | The group's description or summary.                                                | `desc` is a string describing the group.                                                                                                                                             |
+/// | `#[description(desc)]` </br> `#[description = desc]` | The group's description, shown when the help is asked about the group.             | `desc` is a string describing the group.                                                                                                                                             |
+/// | `#[summary(s)]` </br> `#[summary = s]`               | A short, one-line description shown next to the group in the help's listing.       | `s` is a string.                                                                                                                                                                     |
+/// | `#[bucket(name)]` </br> `#[bucket = name]`           | The bucket shared by the group's commands, which are then rate limited together.   | `name` is a string containing the bucket's name. A command's own `bucket` takes precedence; the group's applies to its direct commands, not to those of its sub groups.              |
+/// | `#[default_min_args(min)]` </br> `#[default_max_args(max)]` | Argument bounds for member commands that don't declare their own `min_args`/`max_args`. | `min` and `max` are 16-bit, unsigned integers, or identifiers of `u16` constants in scope. A command's own bound always takes precedence; the defaults apply to the group's direct commands, not to those of its sub groups. |
+/// | `#[options(opts)]`                                   | Use an existing set of group options as-is.                                        | `opts` is an identifier referencing a `GroupOptions` static. Cannot be combined with any other option.                                                                              |
 ///
 /// Similarly to [`command`], this macro generates static instances of the group
 /// and its options. The identifiers of these instances are based off the name of the struct to differentiate
@@ -585,21 +1356,107 @@ pub fn help(attr: TokenStream, input: TokenStream) -> TokenStream {
 /// used in the help command for display and browsing of the group.
 /// It may also be passed as an argument to the macro. For example: `#[group("Banana Phone")]`.
 ///
+/// ## Arguments
+///
+/// Following the name, the `#[group]` attribute accepts arguments that change what is generated:
+///
+/// | Syntax | Description                                                                                                                                                                                                         |
+/// | ------ | ------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------- |
+/// | `meta` | Also generate `pub fn foo_group_commands() -> &'static [&'static Command]` and `pub fn foo_group_meta() -> &'static GroupOptions`, where `foo` is the struct's name in snake case, exposing the group's structure. |
+/// | `static_case = c` | Case the generated statics' names with `c`, as with the [`command`] argument of the same name. Commands, sub groups and the default command are referred to with the same case. |
+///
 /// [`command`]: #fn.command.html
 
 #[proc_macro_attribute]
 pub fn group(attr: TokenStream, input: TokenStream) -> TokenStream {
-    let group = parse_macro_input!(input as GroupStruct);
+    let mut group = parse_macro_input!(input as GroupStruct);
 
-    let name = if !attr.is_empty() {
-        parse_macro_input!(attr as Lit).to_str()
-    } else {
-        group.name.to_string()
+    let AttributeArgs { name, values } = parse_macro_input!(attr as AttributeArgs);
+
+    let name = match name {
+        Some(lit) => lit.to_str(),
+        None => group.name.unraw().to_string(),
     };
 
+    let mut args = GroupArgs::default();
+
+    for values in values {
+        let span = values.span;
+
+        let name = values.name.to_string();
+        let name = &name[..];
+
+        // Any other argument is a group option, as if it were given as an attribute.
+        if name != "meta" && name != "static_case" {
+            group.attributes.push(parse_quote!(#[#values]));
+
+            continue;
+        }
+
+        match_options!(name, values, args, span => [
+            meta;
+            static_case
+        ]);
+    }
+
+    if let Some(attribute) = group.attributes.iter().find(|a| a.path.is_ident("options")) {
+        if group.module.is_some() {
+            return Error::new(
+                attribute.span(),
+                "`options` cannot be used on a module, as the module's commands would be left out",
+            )
+            .to_compile_error()
+            .into();
+        }
+
+        if group.attributes.len() > 1 {
+            return Error::new(
+                attribute.span(),
+                "`options` references a `GroupOptions` static and cannot be combined with inline options",
+            )
+            .to_compile_error()
+            .into();
+        }
+
+        let values = propagate_err!(parse_values(attribute));
+        let options: Ident = propagate_err!(attributes::parse(values));
+
+        let cooked = group.cooked.clone();
+        let n = group.name.with_cased_suffix(GROUP, args.static_case);
+        let allow_case = args.static_case.lint_allowance();
+        let group_path = quote!(serenity::framework::standard::CommandGroup);
+        let meta = group_meta(&args, &group, &options);
+
+        return (quote! {
+            #(#cooked)*
+            #allow_case
+            pub static #n: #group_path = #group_path {
+                name: #name,
+                options: &#options,
+            };
+
+            #meta
+
+            #group
+        })
+        .into();
+    }
+
     let mut options = GroupOptions::new();
 
     for attribute in &group.attributes {
+        if attribute.path.is_ident("commands") {
+            options.commands = propagate_err!(CommandRef::parse_list(attribute));
+
+            continue;
+        }
+
+        if attribute.path.is_ident("order") {
+            options.order = propagate_err!(parse_signed(attribute));
+
+            continue;
+        }
+
         let span = attribute.span();
         let values = propagate_err!(parse_values(attribute));
 
@@ -631,7 +1488,10 @@ pub fn group(attr: TokenStream, input: TokenStream) -> TokenStream {
                 required_permissions;
                 checks;
                 default_command;
-                commands;
+                default_min_args;
+                default_max_args;
+                bucket;
+                summary;
                 sub_groups
             ]),
         }
@@ -647,38 +1507,56 @@ pub fn group(attr: TokenStream, input: TokenStream) -> TokenStream {
         required_permissions,
         checks,
         default_command,
+        default_min_args,
+        default_max_args,
+        bucket,
         description,
-        commands,
+        summary,
+        order,
+        mut commands,
         sub_groups,
     } = options;
 
+    commands.extend(group.module_commands());
+
     let cooked = group.cooked.clone();
     let cooked2 = cooked.clone();
 
-    let n = group.name.with_suffix(GROUP);
+    let case = args.static_case;
+    let allow_case = case.lint_allowance();
+    let n = group.name.with_cased_suffix(GROUP, case);
 
     let default_command = default_command.map(|ident| {
-        let i = ident.with_suffix(COMMAND);
+        let i = ident.with_cased_suffix(COMMAND, case);
 
         quote!(&#i)
     });
 
     let commands = commands
         .into_iter()
-        .map(|c| c.with_suffix(COMMAND))
+        .map(|CommandRef { cfgs, module, name }| {
+            let i = name.with_cased_suffix(COMMAND, case);
+
+            match module {
+                Some(module) => quote!(#(#cfgs)* &#module::#i),
+                None => quote!(#(#cfgs)* &#i),
+            }
+        })
         .collect::<Vec<_>>();
 
     let sub_groups = sub_groups
         .into_iter()
-        .map(|c| c.with_suffix(GROUP))
+        .map(|c| c.with_cased_suffix(GROUP, case))
         .collect::<Vec<_>>();
 
-    let options = group.name.with_suffix(GROUP_OPTIONS);
+    let options = group.name.with_cased_suffix(GROUP_OPTIONS, case);
     let options_path = quote!(serenity::framework::standard::GroupOptions);
     let group_path = quote!(serenity::framework::standard::CommandGroup);
+    let meta = group_meta(&args, &group, &options);
 
     (quote! {
         #(#cooked)*
+        #allow_case
         pub static #options: #options_path = #options_path {
             prefixes: &[#(#prefixes),*],
             only_in: #only_in,
@@ -689,22 +1567,56 @@ pub fn group(attr: TokenStream, input: TokenStream) -> TokenStream {
             required_permissions: #required_permissions,
             checks: #checks,
             default_command: #default_command,
+            default_min_args: #default_min_args,
+            default_max_args: #default_max_args,
+            bucket: #bucket,
             description: #description,
-            commands: &[#(&#commands),*],
+            summary: #summary,
+            order: #order,
+            commands: &[#(#commands),*],
             sub_groups: &[#(&#sub_groups),*],
         };
 
         #(#cooked2)*
+        #allow_case
         pub static #n: #group_path = #group_path {
             name: #name,
             options: &#options,
         };
 
+        #meta
+
         #group
     })
     .into()
 }
 
+/// Generates the accessors of a group's structure, if requested with `#[group(meta)]`.
+fn group_meta(args: &GroupArgs, group: &GroupStruct, options: &Ident) -> proc_macro2::TokenStream {
+    if !args.meta {
+        return quote!();
+    }
+
+    let cooked = &group.cooked;
+    let name = to_snake_case(&group.name.unraw().to_string());
+    let commands = format_ident!("{}_group_commands", name);
+    let meta = format_ident!("{}_group_meta", name);
+
+    quote! {
+        #(#cooked)*
+        #[allow(dead_code)]
+        pub fn #commands() -> &'static [&'static serenity::framework::standard::Command] {
+            #options.commands
+        }
+
+        #(#cooked)*
+        #[allow(dead_code)]
+        pub fn #meta() -> &'static serenity::framework::standard::GroupOptions {
+            &#options
+        }
+    }
+}
+
 /// A macro for marking a function as a condition checker to groups and commands.
 ///
 /// ## Options
@@ -1,14 +1,15 @@
-use crate::consts::CHECK;
-use crate::util::{Argument, AsOption, IdentExt2, Parenthesised};
+use crate::attributes::parse_values;
+use crate::consts::{CHECK, COLLAPSED_TEXT_TOKENS, EMBED_MAX_FIELDS, EMBED_MAX_FIELD_LENGTH, HELP_TEXT_TOKENS, OPTIONAL_COMMAND_FIELDS};
+use crate::util::{to_snake_case, Argument, AsOption, IdentExt2, LitExt, Parenthesised};
 use proc_macro2::Span;
 use proc_macro2::TokenStream as TokenStream2;
-use quote::{quote, ToTokens};
+use quote::{quote, quote_spanned, ToTokens};
 use syn::{
-    braced,
+    braced, bracketed, parse_quote,
     parse::{Error, Parse, ParseStream, Result},
     spanned::Spanned,
     punctuated::Punctuated,
-    Attribute, Block, FnArg, Ident, Pat, Path, PathSegment, ReturnType, Stmt, Expr, ExprClosure,
+    token, Attribute, Block, FnArg, Ident, Item, Lit, LitByteStr, LitStr, Pat, Path, PathSegment, ReturnType, Stmt, Expr, ExprClosure, ExprLit,
     Token, Type, Visibility,
 };
 use std::str::FromStr;
@@ -18,6 +19,7 @@ pub enum OnlyIn {
     Dm,
     Guild,
     None,
+    Both,
 }
 
 impl OnlyIn {
@@ -26,6 +28,8 @@ impl OnlyIn {
         match s {
             "guilds" | "guild" => Ok(OnlyIn::Guild),
             "dms" | "dm" => Ok(OnlyIn::Dm),
+            "both" => Ok(OnlyIn::Both),
+            "inherit" => Ok(OnlyIn::None),
             _ => Err(Error::new(span, "invalid restriction type")),
         }
     }
@@ -38,6 +42,7 @@ impl ToTokens for OnlyIn {
             OnlyIn::Dm => stream.extend(quote!(#only_in_path::Dm)),
             OnlyIn::Guild => stream.extend(quote!(#only_in_path::Guild)),
             OnlyIn::None => stream.extend(quote!(#only_in_path::None)),
+            OnlyIn::Both => stream.extend(quote!(#only_in_path::Both)),
         }
     }
 }
@@ -102,9 +107,20 @@ fn is_cooked(attr: &Attribute) -> bool {
         "warn",
         "deny",
         "forbid",
+        "track_caller",
     ];
 
-    COOKED_ATTRIBUTE_NAMES.iter().any(|n| attr.path.is_ident(n))
+    // Paths with several segments, such as tool attributes (`#[rustfmt::skip]`),
+    // can never name one of our options.
+    attr.path.segments.len() > 1 || COOKED_ATTRIBUTE_NAMES.iter().any(|n| attr.path.is_ident(n))
+}
+
+/// Test if the cooked attribute may also be applied to the generated statics,
+/// rather than only the function.
+pub fn is_static_compatible(attr: &Attribute) -> bool {
+    const STATIC_ATTRIBUTE_NAMES: &[&str] = &["cfg", "cfg_attr", "allow", "warn", "deny", "forbid"];
+
+    STATIC_ATTRIBUTE_NAMES.iter().any(|n| attr.path.is_ident(n))
 }
 
 /// Removes cooked attributes from a vector of attributes. Uncooked attributes are left in the vector.
@@ -133,6 +149,8 @@ fn remove_cooked(attrs: &mut Vec<Attribute>) -> Vec<Attribute> {
 pub struct CommandFun {
     /// `#[...]`-style attributes.
     pub attributes: Vec<Attribute>,
+    /// Whether each of the `attributes` is a documentation comment, renamed to `#[description]`.
+    pub doc_comments: Vec<bool>,
     /// Populated cooked attributes. These are attributes outside of the realm of this crate's procedural macros
     /// and will appear in generated output.
     pub cooked: Vec<Attribute>,
@@ -147,17 +165,29 @@ impl Parse for CommandFun {
     fn parse(input: ParseStream<'_>) -> Result<Self> {
         let mut attributes = input.call(Attribute::parse_outer)?;
 
+        let mut docs = Vec::with_capacity(attributes.len());
+
         // `#[doc = "..."]` is a cooked attribute but it is special-cased for commands.
         for attr in &mut attributes {
             // Rename documentation comment attributes (`#[doc = "..."]`) to `#[description = "..."]`.
-            if attr.path.is_ident("doc") {
+            let doc = attr.path.is_ident("doc");
+
+            if doc {
                 attr.path = Path::from(PathSegment::from(Ident::new(
                     "description",
                     Span::call_site(),
                 )));
             }
+
+            docs.push(doc);
         }
 
+        let doc_comments = attributes
+            .iter()
+            .zip(docs)
+            .filter(|(attr, _)| !is_cooked(attr))
+            .map(|(_, doc)| doc)
+            .collect();
         let cooked = remove_cooked(&mut attributes);
 
         let visibility = input.parse::<Visibility>()?;
@@ -190,6 +220,7 @@ impl Parse for CommandFun {
 
         Ok(Self {
             attributes,
+            doc_comments,
             cooked,
             visibility,
             name,
@@ -204,6 +235,7 @@ impl ToTokens for CommandFun {
     fn to_tokens(&self, stream: &mut TokenStream2) {
         let Self {
             attributes: _,
+            doc_comments: _,
             cooked,
             visibility,
             name,
@@ -331,12 +363,29 @@ fn parse_closure_hook(
     })
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, PartialEq)]
 pub struct Permissions(pub u64);
 
+/// Shorthands accepted in place of a permission's full name.
+const PERMISSION_ALIASES: &[(&str, &str)] = &[
+    ("ADMIN", "ADMINISTRATOR"),
+    ("KICK", "KICK_MEMBERS"),
+    ("BAN", "BAN_MEMBERS"),
+    ("MUTE", "MUTE_MEMBERS"),
+    ("DEAFEN", "DEAFEN_MEMBERS"),
+    ("MOVE", "MOVE_MEMBERS"),
+    ("MANAGE_SERVER", "MANAGE_GUILD"),
+];
+
 impl Permissions {
     pub fn from_str(s: &str) -> Option<Self> {
-        Some(Permissions(match s.to_uppercase().as_str() {
+        let s = s.to_uppercase();
+        let name = PERMISSION_ALIASES
+            .iter()
+            .find(|(alias, _)| *alias == s)
+            .map_or(s.as_str(), |(_, name)| *name);
+
+        Some(Permissions(match name {
             "PRESET_GENERAL" => 0b0000_0110_0011_0111_1101_1100_0100_0001,
             "PRESET_TEXT" => 0b0000_0000_0000_0111_1111_1100_0100_0000,
             "PRESET_VOICE" => 0b0000_0011_1111_0000_0000_0000_0000_0000,
@@ -349,6 +398,7 @@ impl Permissions {
             "ADD_REACTIONS" => 0b0000_0000_0000_0000_0000_0000_0100_0000,
             "VIEW_AUDIT_LOG" => 0b0000_0000_0000_0000_0000_0000_1000_0000,
             "PRIORITY_SPEAKER" => 0b0000_0000_0000_0000_0000_0001_0000_0000,
+            "STREAM" => 0b0000_0000_0000_0000_0000_0010_0000_0000,
             "READ_MESSAGES" => 0b0000_0000_0000_0000_0000_0100_0000_0000,
             "SEND_MESSAGES" => 0b0000_0000_0000_0000_0000_1000_0000_0000,
             "SEND_TTS_MESSAGES" => 0b0000_0000_0000_0000_0001_0000_0000_0000,
@@ -386,12 +436,16 @@ impl ToTokens for Permissions {
     }
 }
 
-#[derive(Debug, Default, Clone, Copy, PartialEq)]
-pub struct Colour(pub u32);
+/// A colour, given either as a value or as the path of a `u32` constant.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Colour {
+    Value(u32),
+    Const(Path),
+}
 
 impl Colour {
     pub fn from_str(s: &str) -> Option<Self> {
-        Some(Colour(match s.to_uppercase().as_str() {
+        Some(Colour::Value(match s.to_uppercase().as_str() {
             "BLITZ_BLUE" => 0x6FC6E2,
             "BLUE" => 0x3498DB,
             "BLURPLE" => 0x7289DA,
@@ -427,15 +481,219 @@ impl Colour {
 
 impl ToTokens for Colour {
     fn to_tokens(&self, stream: &mut TokenStream2) {
-        let value = self.0;
         let path = quote!(serenity::utils::Colour);
 
+        // `Colour::from` cannot be called in a static, so constants must be `u32`s.
+        stream.extend(match self {
+            Colour::Value(value) => quote!(#path(#value)),
+            Colour::Const(constant) => quote!(#path(#constant)),
+        });
+    }
+}
+
+/// A bound on the amount of arguments, given either as an integer or as the name of a `u16` constant.
+#[derive(Debug, Clone)]
+pub enum ArgsBound {
+    Num(u16),
+    Const(Ident),
+}
+
+impl ToTokens for ArgsBound {
+    fn to_tokens(&self, stream: &mut TokenStream2) {
+        match self {
+            ArgsBound::Num(n) => n.to_tokens(stream),
+            ArgsBound::Const(c) => c.to_tokens(stream),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum DescriptionPart {
+    Text(String),
+    /// Path to a file whose contents are included with `include_str!`.
+    Include(LitStr),
+    /// Name of an environment variable read at compile time with `env!`.
+    Env(LitStr),
+}
+
+impl ToTokens for DescriptionPart {
+    fn to_tokens(&self, stream: &mut TokenStream2) {
+        match self {
+            DescriptionPart::Text(s) => s.to_tokens(stream),
+            DescriptionPart::Include(path) => stream.extend(quote!(include_str!(#path))),
+            DescriptionPart::Env(var) => stream.extend(quote!(env!(#var))),
+        }
+    }
+}
+
+/// An example invocation of a command, paired with the output it is expected to produce
+/// and the permissions of the users it is relevant to.
+/// The output is empty and no permissions are required if they were not given.
+#[derive(Debug, Default, PartialEq)]
+pub struct Example {
+    pub input: String,
+    pub output: String,
+    pub requires: Permissions,
+}
+
+/// Emits the example as an `(input, output)` pair; its `requires` are emitted separately,
+/// as `example_permissions`.
+impl ToTokens for Example {
+    fn to_tokens(&self, stream: &mut TokenStream2) {
+        let Example { input, output, .. } = self;
+
+        stream.extend(quote!((#input, #output)));
+    }
+}
+
+/// Labels under which a command is reported to metrics, set by `#[metrics]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Metrics {
+    pub name: String,
+    pub tags: Vec<String>,
+}
+
+impl ToTokens for Metrics {
+    fn to_tokens(&self, stream: &mut TokenStream2) {
+        let Metrics { name, tags } = self;
+
         stream.extend(quote! {
-            #path(#value)
+            serenity::framework::standard::CommandMetrics {
+                name: #name,
+                tags: &[#(#tags),*],
+            }
         });
     }
 }
 
+/// A value of `Context::data` a command requires, declared by `#[requires_data(Key)]`
+/// or `#[requires_data(binding: Key)]`.
+#[derive(Debug)]
+pub struct RequiredData {
+    pub binding: Ident,
+    pub key: Path,
+}
+
+impl Parse for RequiredData {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let binding = if input.peek(Ident) && input.peek2(Token![:]) && !input.peek2(Token![::]) {
+            let binding = input.parse()?;
+            input.parse::<Token![:]>()?;
+
+            Some(binding)
+        } else {
+            None
+        };
+
+        let key: Path = input.parse()?;
+
+        let binding = match binding {
+            Some(binding) => binding,
+            None => {
+                let last = &key.segments.last().unwrap().ident;
+
+                Ident::new(&to_snake_case(&syn::ext::IdentExt::unraw(last).to_string()), last.span())
+            },
+        };
+
+        Ok(Self { binding, key })
+    }
+}
+
+impl RequiredData {
+    /// The statement binding the value, cloned out of the data of `ctx`.
+    ///
+    /// It returns an error from the command if the value is absent.
+    pub fn fetch(&self, ctx: &Ident) -> Stmt {
+        let RequiredData { binding, key } = self;
+
+        let name = key
+            .segments
+            .iter()
+            .map(|s| syn::ext::IdentExt::unraw(&s.ident).to_string())
+            .collect::<Vec<_>>()
+            .join("::");
+        let missing = format!("`{}` is missing from the context's data", name);
+
+        parse_quote! {
+            let #binding = {
+                let data = #ctx.data.read().await;
+
+                match data.get::<#key>() {
+                    ::std::option::Option::Some(value) => ::std::clone::Clone::clone(value),
+                    ::std::option::Option::None => {
+                        return ::std::result::Result::Err(serenity::framework::standard::CommandError::from(#missing));
+                    },
+                }
+            };
+        }
+    }
+}
+
+/// A command's description, assembled from its `#[description]` attributes.
+/// Each part is separated from the previous one by a newline.
+#[derive(Debug, Default)]
+pub struct Description(pub Vec<DescriptionPart>);
+
+impl Description {
+    pub fn push(&mut self, part: DescriptionPart) {
+        let part = match part {
+            DescriptionPart::Text(s) if !self.0.is_empty() => {
+                DescriptionPart::Text(s.trim_matches(' ').to_string())
+            }
+            part => part,
+        };
+
+        self.0.push(part);
+    }
+
+    /// Whether the description is missing or consists only of blank text.
+    pub fn is_blank(&self) -> bool {
+        self.0.iter().all(|part| match part {
+            DescriptionPart::Text(s) => s.trim().is_empty(),
+            _ => false,
+        })
+    }
+}
+
+impl ToTokens for Description {
+    fn to_tokens(&self, stream: &mut TokenStream2) {
+        if self.0.is_empty() {
+            return stream.extend(quote!(None));
+        }
+
+        let texts = self
+            .0
+            .iter()
+            .map(|p| match p {
+                DescriptionPart::Text(s) => Some(&s[..]),
+                DescriptionPart::Include(_) | DescriptionPart::Env(_) => None,
+            })
+            .collect::<Option<Vec<_>>>();
+
+        match texts {
+            Some(texts) => {
+                let desc = texts.join("\n");
+
+                stream.extend(quote!(Some(#desc)));
+            }
+            None => {
+                let mut parts = Vec::with_capacity(self.0.len() * 2);
+
+                for (i, part) in self.0.iter().enumerate() {
+                    if i != 0 {
+                        parts.push(quote!("\n"));
+                    }
+
+                    parts.push(quote!(#part));
+                }
+
+                stream.extend(quote!(Some(concat!(#(#parts),*))));
+            }
+        }
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct Checks(pub Vec<Ident>);
 
@@ -450,21 +708,48 @@ impl ToTokens for Checks {
 #[derive(Debug, Default)]
 pub struct Options {
     pub checks: Checks,
+    pub priority_checks: Checks,
+    pub inherit_group_checks: bool,
     pub bucket: AsOption<String>,
+    pub cooldown_bypass_roles: Vec<String>,
+    pub cooldown_bypass_role_ids: Vec<u64>,
     pub aliases: Vec<String>,
-    pub description: AsOption<String>,
+    pub prefixes: Vec<String>,
+    pub description: Description,
+    pub category: AsOption<String>,
     pub delimiters: Vec<String>,
     pub usage: AsOption<String>,
-    pub examples: Vec<String>,
-    pub min_args: AsOption<u16>,
-    pub max_args: AsOption<u16>,
+    pub examples: Vec<Example>,
+    pub localized_names: Vec<(String, String)>,
+    pub localized_usages: Vec<(String, String)>,
+    pub required_data: Vec<RequiredData>,
+    pub min_args: AsOption<ArgsBound>,
+    pub max_args: AsOption<ArgsBound>,
+    pub base_min_args: AsOption<ArgsBound>,
+    pub min_membership_seconds: AsOption<u64>,
+    pub timeout: AsOption<u64>,
     pub allowed_roles: Vec<String>,
+    pub guilds: Vec<u64>,
+    pub allowed_users: Vec<u64>,
+    pub slash_compatible: bool,
     pub required_permissions: Permissions,
     pub help_available: bool,
+    pub help_unavailable_reason: AsOption<String>,
     pub only_in: OnlyIn,
+    pub delete_invocation: bool,
+    pub typing: bool,
+    pub requires_voice: bool,
+    pub requires_age_gated_guild: bool,
     pub owners_only: bool,
     pub owner_privilege: bool,
     pub sub_commands: Vec<Ident>,
+    pub on_error: AsOption<Ident>,
+    pub unknown_sub: AsOption<Ident>,
+    pub num_delimiters: AsOption<u16>,
+    pub delimiter_escape: AsOption<char>,
+    pub quotes: AsOption<Vec<char>>,
+    pub deprecation_notice: AsOption<String>,
+    pub metrics: AsOption<Metrics>,
 }
 
 impl Options {
@@ -473,9 +758,184 @@ impl Options {
         let mut options = Self::default();
 
         options.help_available = true;
+        options.inherit_group_checks = true;
 
         options
     }
+
+    /// Marks the command as deprecated, with `notice` or else a generic notice.
+    pub fn deprecate(&mut self, notice: Option<String>) {
+        let notice = notice.unwrap_or_else(|| "This command is deprecated.".to_string());
+
+        self.deprecation_notice = AsOption(Some(notice));
+    }
+
+    /// Fails if the command is listed in the help but has no description.
+    pub fn require_description(&self, name: &Ident) -> Result<()> {
+        if self.help_available && self.description.is_blank() {
+            return Err(Error::new(
+                name.span(),
+                format_args!(
+                    "the command `{}` is listed in the help but has no description; add doc comments or a `#[description]`",
+                    syn::ext::IdentExt::unraw(name)
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Fails if the command has an `unknown_sub` handler without `sub_commands`.
+    pub fn check_unknown_sub(&self) -> Result<()> {
+        match (&self.unknown_sub.0, self.sub_commands.is_empty()) {
+            (Some(handler), true) => Err(Error::new(
+                handler.span(),
+                "`unknown_sub` requires the command to have `sub_commands`",
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Fails if the command named `name` lists itself in its `sub_commands`.
+    pub fn reject_self_sub_command(&self, name: &Ident) -> Result<()> {
+        let name = syn::ext::IdentExt::unraw(name);
+
+        match self.sub_commands.iter().find(|s| syn::ext::IdentExt::unraw(*s) == name) {
+            Some(sub) => Err(Error::new(sub.span(), "a command cannot be its own sub command")),
+            None => Ok(()),
+        }
+    }
+
+    /// Fails if one of the command's `prefixes` is empty.
+    pub fn check_prefixes(&self, attributes: &[Attribute]) -> Result<()> {
+        if self.prefixes.iter().all(|p| !p.is_empty()) {
+            return Ok(());
+        }
+
+        let span = attributes
+            .iter()
+            .find(|a| a.path.is_ident("prefixes"))
+            .map_or_else(Span::call_site, |a| a.span());
+
+        Err(Error::new(span, "a command's prefixes cannot be empty"))
+    }
+
+    /// Fails if `num_delimiters` is given without the command's own `delimiters`.
+    pub fn check_num_delimiters(&self, attributes: &[Attribute]) -> Result<()> {
+        if self.num_delimiters.0.is_none() || !self.delimiters.is_empty() {
+            return Ok(());
+        }
+
+        let span = attributes
+            .iter()
+            .find(|a| a.path.is_ident("num_delimiters"))
+            .map_or_else(Span::call_site, |a| a.span());
+
+        Err(Error::new(
+            span,
+            "`num_delimiters` requires the command to declare its own `delimiters`",
+        ))
+    }
+}
+
+/// Arguments to the `#[command]` attribute itself, altering how the command's items are generated.
+#[derive(Debug, Default)]
+pub struct CommandArgs {
+    pub fast_lookup: bool,
+    pub name_matcher: bool,
+    pub test: bool,
+    pub preset: Option<Ident>,
+    pub names: Vec<String>,
+    pub as_const: bool,
+    pub static_case: StaticCase,
+    pub expose_name: bool,
+    pub options_from: AsOption<Ident>,
+    pub inline_options: bool,
+    pub introspect: bool,
+    pub fn_static: AsOption<String>,
+    pub feature: AsOption<String>,
+}
+
+impl CommandArgs {
+    /// The name the command's statics are derived from: `fn_static` if given,
+    /// otherwise the function's name.
+    pub fn static_base(&self, fun_name: &Ident) -> Result<String> {
+        match &self.fn_static.0 {
+            Some(base) if base.trim().is_empty() => {
+                Err(Error::new(Span::call_site(), "`fn_static` cannot be empty"))
+            }
+            Some(base) => Ok(base.clone()),
+            None => Ok(syn::ext::IdentExt::unraw(fun_name).to_string()),
+        }
+    }
+
+    /// The `__COMMAND_NAME` constant declared in the command's body if `expose_name` is set.
+    pub fn name_constant(&self, name: &str) -> TokenStream2 {
+        if !self.expose_name {
+            return TokenStream2::new();
+        }
+
+        quote! {
+            #[allow(dead_code)]
+            const __COMMAND_NAME: &str = #name;
+        }
+    }
+
+    /// The feature the command requires, if any, along with the `#[cfg]` gating its items on it.
+    pub fn feature_gate(&self) -> Result<Option<(String, Attribute)>> {
+        match &self.feature.0 {
+            Some(feature) if feature.trim().is_empty() => {
+                Err(Error::new(Span::call_site(), "`feature` cannot be empty"))
+            }
+            Some(feature) => Ok(Some((feature.clone(), parse_quote!(#[cfg(feature = #feature)])))),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Arguments to the `#[group]` attribute itself, altering how the group's items are generated.
+#[derive(Debug, Default)]
+pub struct GroupArgs {
+    pub meta: bool,
+    pub static_case: StaticCase,
+}
+
+/// How the identifiers of generated statics are cased.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StaticCase {
+    /// `FOO_COMMAND`
+    Screaming,
+    /// `FooCommand`
+    Pascal,
+    /// `foo_command`
+    Snake,
+}
+
+// `#[default]` on variants is not available on the minimum supported Rust version.
+#[allow(clippy::derivable_impls)]
+impl Default for StaticCase {
+    fn default() -> Self {
+        StaticCase::Screaming
+    }
+}
+
+impl StaticCase {
+    pub fn from_str(s: &str) -> Option<Self> {
+        Some(match s.to_lowercase().as_str() {
+            "screaming" => StaticCase::Screaming,
+            "pascal" => StaticCase::Pascal,
+            "snake" => StaticCase::Snake,
+            _ => return None,
+        })
+    }
+
+    /// Silences the lint on statics that are not uppercase.
+    pub fn lint_allowance(self) -> TokenStream2 {
+        match self {
+            StaticCase::Screaming => quote!(),
+            _ => quote!(#[allow(non_upper_case_globals)]),
+        }
+    }
 }
 
 #[derive(PartialEq, Debug)]
@@ -483,6 +943,7 @@ pub enum HelpBehaviour {
     Strike,
     Hide,
     Nothing,
+    Collapse,
 }
 
 impl HelpBehaviour {
@@ -491,6 +952,7 @@ impl HelpBehaviour {
             "strike" => HelpBehaviour::Strike,
             "hide" => HelpBehaviour::Hide,
             "nothing" => HelpBehaviour::Nothing,
+            "collapse" => HelpBehaviour::Collapse,
             _ => return None,
         })
     }
@@ -503,7 +965,107 @@ impl ToTokens for HelpBehaviour {
             HelpBehaviour::Strike => stream.extend(quote!(#help_behaviour_path::Strike)),
             HelpBehaviour::Hide => stream.extend(quote!(#help_behaviour_path::Hide)),
             HelpBehaviour::Nothing => stream.extend(quote!(#help_behaviour_path::Nothing)),
+            HelpBehaviour::Collapse => stream.extend(quote!(#help_behaviour_path::Collapse)),
+        }
+    }
+}
+
+/// How the help orders the groups it lists.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GroupOrder {
+    Declaration,
+    Weight,
+    Name,
+}
+
+impl GroupOrder {
+    pub fn from_str(s: &str) -> Option<Self> {
+        Some(match s.to_lowercase().as_str() {
+            "declaration" => GroupOrder::Declaration,
+            "weight" => GroupOrder::Weight,
+            "name" => GroupOrder::Name,
+            _ => return None,
+        })
+    }
+}
+
+impl ToTokens for GroupOrder {
+    fn to_tokens(&self, stream: &mut TokenStream2) {
+        let path = quote!(serenity::framework::standard::GroupOrder);
+
+        stream.extend(match self {
+            GroupOrder::Declaration => quote!(#path::Declaration),
+            GroupOrder::Weight => quote!(#path::Weight),
+            GroupOrder::Name => quote!(#path::Name),
+        });
+    }
+}
+
+/// Help text which may contain `{token}`s substituted at runtime.
+#[derive(Debug, PartialEq)]
+pub struct HelpText(pub String);
+
+impl HelpText {
+    pub fn validate(s: &str, span: Span) -> Result<()> {
+        validate_tokens(s, span, HELP_TEXT_TOKENS)
+    }
+}
+
+/// Checks that every `{token}` in `s` is one of `tokens`.
+fn validate_tokens(s: &str, span: Span, tokens: &[&str]) -> Result<()> {
+    let mut rest = s;
+
+    while let Some(start) = rest.find('{') {
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => return Err(Error::new(span, "unclosed `{` in help text")),
+        };
+
+        let token = &rest[start + 1..end];
+
+        if !tokens.contains(&token) {
+            return Err(Error::new(
+                span,
+                format_args!(
+                    "unknown token `{{{}}}` in help text; expected one of: {}",
+                    token,
+                    tokens
+                        .iter()
+                        .map(|t| format!("`{{{}}}`", t))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            ));
         }
+
+        rest = &rest[end + 1..];
+    }
+
+    Ok(())
+}
+
+impl ToTokens for HelpText {
+    fn to_tokens(&self, stream: &mut TokenStream2) {
+        self.0.to_tokens(stream);
+    }
+}
+
+/// The singular and plural forms of the line counting a group's collapsed commands.
+/// Both may contain the `{count}` token.
+#[derive(Debug, PartialEq)]
+pub struct CollapsedText(pub String, pub String);
+
+impl CollapsedText {
+    pub fn validate(s: &str, span: Span) -> Result<()> {
+        validate_tokens(s, span, COLLAPSED_TEXT_TOKENS)
+    }
+}
+
+impl ToTokens for CollapsedText {
+    fn to_tokens(&self, stream: &mut TokenStream2) {
+        let CollapsedText(singular, plural) = self;
+
+        stream.extend(quote!((#singular, #plural)));
     }
 }
 
@@ -520,11 +1082,15 @@ pub struct HelpOptions {
     pub sub_commands_label: String,
     pub guild_only_text: String,
     pub checks_label: String,
+    pub deprecated_label: String,
     pub dm_only_text: String,
     pub dm_and_guild_text: String,
     pub available_text: String,
+    pub show_availability: bool,
+    pub show_permissions: bool,
+    pub permissions_label: String,
     pub command_not_found_text: String,
-    pub individual_command_tip: String,
+    pub individual_command_tip: HelpText,
     pub strikethrough_commands_tip_in_dm: Option<String>,
     pub strikethrough_commands_tip_in_guild: Option<String>,
     pub group_prefix: String,
@@ -537,6 +1103,14 @@ pub struct HelpOptions {
     pub embed_success_colour: Colour,
     pub max_levenshtein_distance: usize,
     pub indention_prefix: String,
+    pub max_embed_fields: usize,
+    pub sort_groups_by: GroupOrder,
+    pub max_field_length: usize,
+    pub paginate_after: usize,
+    pub footer_text: HelpText,
+    pub prefix_display: HelpText,
+    pub collapsed_commands_text: CollapsedText,
+    pub locale: String,
 }
 
 impl Default for HelpOptions {
@@ -552,14 +1126,20 @@ impl Default for HelpOptions {
             description_label: "Description".to_string(),
             guild_only_text: "Only in guilds".to_string(),
             checks_label: "Checks".to_string(),
+            deprecated_label: "Deprecated".to_string(),
             sub_commands_label: "Sub Commands".to_string(),
             dm_only_text: "Only in DM".to_string(),
             dm_and_guild_text: "In DM and guilds".to_string(),
             available_text: "Available".to_string(),
+            show_availability: true,
+            show_permissions: false,
+            permissions_label: "Required permissions".to_string(),
             command_not_found_text: "**Error**: Command `{}` not found.".to_string(),
-            individual_command_tip: "To get help with an individual command, pass its \
-                                     name as an argument to this command."
-                .to_string(),
+            individual_command_tip: HelpText(
+                "To get help with an individual command, pass its \
+                 name as an argument to this command."
+                    .to_string(),
+            ),
             group_prefix: "Prefix".to_string(),
             strikethrough_commands_tip_in_dm: None,
             strikethrough_commands_tip_in_guild: None,
@@ -572,6 +1152,17 @@ impl Default for HelpOptions {
             embed_success_colour: Colour::from_str("ROSEWATER").unwrap(),
             max_levenshtein_distance: 0,
             indention_prefix: "-".to_string(),
+            max_embed_fields: EMBED_MAX_FIELDS,
+            sort_groups_by: GroupOrder::Declaration,
+            max_field_length: EMBED_MAX_FIELD_LENGTH,
+            paginate_after: 0,
+            footer_text: HelpText(String::new()),
+            prefix_display: HelpText(String::new()),
+            collapsed_commands_text: CollapsedText(
+                "*{count} hidden command*".to_string(),
+                "*{count} hidden commands*".to_string(),
+            ),
+            locale: String::new(),
         }
     }
 }
@@ -582,6 +1173,47 @@ pub struct GroupStruct {
     pub cooked: Vec<Attribute>,
     pub attributes: Vec<Attribute>,
     pub name: Ident,
+    /// The body of the module, if the group is applied to a `mod` instead of a `struct`.
+    pub module: Option<GroupModule>,
+}
+
+/// The inner attributes and items of a module marked by `#[group]`.
+#[derive(Debug)]
+pub struct GroupModule {
+    pub attributes: Vec<Attribute>,
+    pub items: Vec<Item>,
+}
+
+impl GroupStruct {
+    /// Collects the functions marked by `#[command]` that appear directly in the group's module.
+    ///
+    /// Functions in nested modules are not collected.
+    pub fn module_commands(&self) -> Vec<CommandRef> {
+        let module = match &self.module {
+            Some(module) => module,
+            None => return Vec::new(),
+        };
+
+        module
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Fn(fun) => Some(fun),
+                _ => None,
+            })
+            .filter(|fun| fun.attrs.iter().any(is_command_attribute))
+            .map(|fun| CommandRef {
+                cfgs: fun.attrs.iter().filter(|a| a.path.is_ident("cfg")).cloned().collect(),
+                module: Some(self.name.clone()),
+                name: fun.sig.ident.clone(),
+            })
+            .collect()
+    }
+}
+
+/// Whether the attribute is `#[command]`, written as is or as a path ending with `command`.
+fn is_command_attribute(attr: &Attribute) -> bool {
+    attr.path.segments.last().into_iter().any(|s| s.ident == "command")
 }
 
 impl Parse for GroupStruct {
@@ -592,17 +1224,47 @@ impl Parse for GroupStruct {
 
         let visibility = input.parse()?;
 
-        input.parse::<Token![struct]>()?;
+        let is_module = input.peek(Token![mod]);
+
+        if is_module {
+            let token = input.parse::<Token![mod]>()?;
+
+            if !input.peek2(token::Brace) {
+                return Err(Error::new(
+                    token.span,
+                    "a group module must have a body for its commands to be discovered",
+                ));
+            }
+        } else {
+            input.parse::<Token![struct]>()?;
+        }
 
         let name = input.parse()?;
 
-        input.parse::<Token![;]>()?;
+        let module = if is_module {
+            let content;
+            braced!(content in input);
+
+            let attributes = content.call(Attribute::parse_inner)?;
+
+            let mut items = Vec::new();
+            while !content.is_empty() {
+                items.push(content.parse()?);
+            }
+
+            Some(GroupModule { attributes, items })
+        } else {
+            input.parse::<Token![;]>()?;
+
+            None
+        };
 
         Ok(Self {
             visibility,
             cooked,
             attributes,
             name,
+            module,
         })
     }
 }
@@ -614,15 +1276,242 @@ impl ToTokens for GroupStruct {
             cooked,
             attributes: _,
             name,
+            module,
         } = self;
 
+        stream.extend(match module {
+            Some(GroupModule { attributes, items }) => quote! {
+                #(#cooked)*
+                #visibility mod #name {
+                    #(#attributes)*
+                    #(#items)*
+                }
+            },
+            None => quote! {
+                #(#cooked)*
+                #visibility struct #name;
+            },
+        });
+    }
+}
+
+/// The name of a preset applied with `#[command(preset = name)]`.
+#[derive(Debug)]
+pub struct Preset(pub Ident);
+
+/// The input of `command_preset!`: a name, followed by a braced list of `option: value` pairs.
+#[derive(Debug)]
+pub struct CommandPreset {
+    pub name: Ident,
+    pub attributes: Vec<Attribute>,
+}
+
+impl Parse for CommandPreset {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let name = input.parse::<LitStr>()?;
+        let name = syn::parse_str::<Ident>(&name.value())
+            .map(|i| Ident::new(&i.to_string(), name.span()))
+            .map_err(|_| Error::new(name.span(), "the preset's name must be a valid identifier"))?;
+
+        input.parse::<Token![,]>()?;
+
+        let content;
+        braced!(content in input);
+
+        let mut attributes = Vec::new();
+
+        while !content.is_empty() {
+            let option = content.parse::<Ident>()?;
+            content.parse::<Token![:]>()?;
+
+            let value = if content.peek(token::Bracket) {
+                let list;
+                bracketed!(list in content);
+
+                list.parse::<TokenStream2>()?
+            } else if content.peek(Lit) {
+                content.parse::<Lit>()?.into_token_stream()
+            } else {
+                content.parse::<Path>()?.into_token_stream()
+            };
+
+            let attribute: Attribute = parse_quote!(#[#option(#value)]);
+
+            // Catch malformed values here, where they are written.
+            parse_values(&attribute)?;
+
+            attributes.push(attribute);
+
+            if !content.is_empty() {
+                content.parse::<Token![,]>()?;
+            }
+        }
+
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
+        }
+
+        Ok(Self { name, attributes })
+    }
+}
+
+/// The input of `assert_command_options!`: the path of a command's options,
+/// followed by `field = value` pairs.
+#[derive(Debug)]
+pub struct OptionAssertions {
+    pub options: Path,
+    pub expected: Vec<(Ident, Expr)>,
+}
+
+impl OptionAssertions {
+    /// The value a field is compared to, wrapped in `Some` for optional fields.
+    fn expected_value(field: &Ident, value: &Expr) -> Expr {
+        let explicit = match value {
+            Expr::Path(p) => p.path.is_ident("None"),
+            Expr::Call(call) => matches!(&*call.func, Expr::Path(p) if p.path.is_ident("Some")),
+            _ => false,
+        };
+
+        if !explicit && OPTIONAL_COMMAND_FIELDS.iter().any(|f| field == f) {
+            parse_quote!(Some(#value))
+        } else {
+            value.clone()
+        }
+    }
+
+    /// A constant expression telling whether `actual` holds `value`, used as a pattern.
+    /// `str`s cannot be matched at compile time, so string literals are matched
+    /// against the bytes of `actual` instead.
+    fn matches(actual: TokenStream2, value: &Expr) -> TokenStream2 {
+        match value {
+            Expr::Lit(ExprLit { lit: Lit::Str(s), .. }) => {
+                let bytes = LitByteStr::new(s.value().as_bytes(), s.span());
+
+                quote!(match #actual.as_bytes() { #bytes => true, _ => false })
+            }
+            Expr::Call(call) if call.args.len() == 1
+                && matches!(&*call.func, Expr::Path(p) if p.path.is_ident("Some")) =>
+            {
+                let inner = Self::matches(quote!(value), &call.args[0]);
+
+                quote!(match #actual { Some(value) => #inner, _ => false })
+            }
+            _ => quote!(match #actual { #value => true, _ => false }),
+        }
+    }
+}
+
+impl Parse for OptionAssertions {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let options = input.parse::<Path>()?;
+        let mut expected: Vec<(Ident, Expr)> = Vec::new();
+
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+
+            if input.is_empty() {
+                break;
+            }
+
+            let field = input.parse::<Ident>()?;
+            input.parse::<Token![=]>()?;
+            let value = input.parse::<Expr>()?;
+
+            if expected.iter().any(|(f, _)| *f == field) {
+                return Err(Error::new(
+                    field.span(),
+                    format_args!("`{}` is asserted more than once", field),
+                ));
+            }
+
+            expected.push((field, value));
+        }
+
+        if expected.is_empty() {
+            return Err(Error::new(
+                options.span(),
+                "expected at least one `field = value` to assert",
+            ));
+        }
+
+        Ok(Self { options, expected })
+    }
+}
+
+impl ToTokens for OptionAssertions {
+    fn to_tokens(&self, stream: &mut TokenStream2) {
+        let path = &self.options;
+
+        // Each field's check is named after the field, and a failed one is reported at it.
+        let assertions = self.expected.iter().map(|(field, value)| {
+            let expected = Self::expected_value(field, value);
+            let matches = Self::matches(quote!(#path.#field), &expected);
+
+            quote_spanned! {field.span()=>
+                #[allow(non_upper_case_globals)]
+                #[deny(unreachable_patterns)]
+                const #field: bool = #matches;
+                serenity::static_assertions::const_assert!(#field);
+            }
+        });
+
         stream.extend(quote! {
-            #(#cooked)*
-            #visibility struct #name;
+            const _: () = {
+                const _: &serenity::framework::standard::CommandOptions = &#path;
+
+                #(#assertions)*
+            };
         });
     }
 }
 
+/// A command listed in a group's `#[commands]`, along with the `#[cfg]`s gating it.
+#[derive(Debug)]
+pub struct CommandRef {
+    pub cfgs: Vec<Attribute>,
+    /// The module the command was discovered in, for groups applied to a `mod`.
+    pub module: Option<Ident>,
+    pub name: Ident,
+}
+
+impl Parse for CommandRef {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let cfgs = input.call(Attribute::parse_outer)?;
+
+        if let Some(attr) = cfgs.iter().find(|a| !a.path.is_ident("cfg")) {
+            return Err(Error::new(attr.span(), "only `#[cfg]` may be attached to a command"));
+        }
+
+        let name = if input.peek(Lit) {
+            input.parse::<Lit>()?.to_ident()
+        } else {
+            input.parse::<Ident>()?
+        };
+
+        Ok(CommandRef {
+            cfgs,
+            module: None,
+            name,
+        })
+    }
+}
+
+impl CommandRef {
+    /// Parses the list of a `#[commands(...)]` attribute.
+    pub fn parse_list(attr: &Attribute) -> Result<Vec<Self>> {
+        let list = attr.parse_args_with(Punctuated::<Self, Token![,]>::parse_terminated)?;
+
+        if list.is_empty() {
+            return Err(Error::new(
+                attr.span(),
+                "list cannot be empty; remove `#[commands()]` if it is not needed",
+            ));
+        }
+
+        Ok(list.into_iter().collect())
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct GroupOptions {
     pub prefixes: Vec<String>,
@@ -634,8 +1523,13 @@ pub struct GroupOptions {
     pub required_permissions: Permissions,
     pub checks: Checks,
     pub default_command: AsOption<Ident>,
+    pub default_min_args: AsOption<ArgsBound>,
+    pub default_max_args: AsOption<ArgsBound>,
+    pub bucket: AsOption<String>,
     pub description: AsOption<String>,
-    pub commands: Vec<Ident>,
+    pub summary: AsOption<String>,
+    pub order: i32,
+    pub commands: Vec<CommandRef>,
     pub sub_groups: Vec<Ident>,
 }
 
@@ -649,3 +1543,607 @@ impl GroupOptions {
         options
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{ArgsBound, CollapsedText, CommandArgs, CommandFun, CommandRef, DescriptionPart, GroupOptions, GroupStruct, HelpBehaviour, HelpOptions, HelpText, OnlyIn, OptionAssertions, Options, Permissions, RequiredData, StaticCase};
+    use crate::consts::{COMMAND, COMMAND_OPTIONS, OPTIONAL_COMMAND_FIELDS};
+    use crate::util::{name_with_case, AsOption};
+    use crate::attributes::{parse, parse_values};
+    use proc_macro2::Span;
+    use quote::{quote, ToTokens};
+    use syn::{ext::IdentExt, parse_quote, Attribute, Ident, Item, Type};
+
+    #[test]
+    fn command_fun_keeps_rust_attributes() {
+        let fun: CommandFun = syn::parse_str(
+            "#[aliases(\"p\")] #[allow(clippy::too_many_arguments)] #[rustfmt::skip] \
+             async fn ping() -> CommandResult { Ok(()) }",
+        )
+        .unwrap();
+
+        assert_eq!(fun.attributes.len(), 1);
+        assert!(fun.attributes[0].path.is_ident("aliases"));
+        assert_eq!(fun.cooked.len(), 2);
+    }
+
+    #[test]
+    fn command_fun_keeps_track_caller() {
+        let fun: CommandFun = syn::parse_str(
+            "#[track_caller] #[description = \"Panics.\"] async fn boom() -> CommandResult { Ok(()) }",
+        )
+        .unwrap();
+
+        assert_eq!(fun.cooked.len(), 1);
+        assert!(fun.cooked[0].path.is_ident("track_caller"));
+        assert!(!super::is_static_compatible(&fun.cooked[0]));
+    }
+
+    #[test]
+    fn help_fun_keeps_cfgs() {
+        let fun: CommandFun = syn::parse_str(
+            "#[cfg(feature = \"help\")] #[suggestion_text = \"Did you mean `{}`?\"] \
+             async fn my_help() -> CommandResult { Ok(()) }",
+        )
+        .unwrap();
+
+        assert_eq!(fun.attributes.len(), 1);
+        assert!(fun.attributes[0].path.is_ident("suggestion_text"));
+        assert_eq!(fun.cooked.len(), 1);
+        assert!(super::is_static_compatible(&fun.cooked[0]));
+    }
+
+    #[test]
+    fn help_behaviour_values() {
+        assert_eq!(HelpBehaviour::from_str("strike"), Some(HelpBehaviour::Strike));
+        assert_eq!(HelpBehaviour::from_str("Hide"), Some(HelpBehaviour::Hide));
+        assert_eq!(HelpBehaviour::from_str("nothing"), Some(HelpBehaviour::Nothing));
+        assert_eq!(HelpBehaviour::from_str("collapse"), Some(HelpBehaviour::Collapse));
+        assert_eq!(HelpBehaviour::from_str("fold"), None);
+    }
+
+    #[test]
+    fn permission_aliases() {
+        let bits = |s| Permissions::from_str(s).map(|p| p.0);
+
+        assert_eq!(bits("ADMIN"), bits("ADMINISTRATOR"));
+        assert_eq!(bits("kick"), bits("KICK_MEMBERS"));
+        assert_eq!(bits("Ban"), bits("BAN_MEMBERS"));
+        assert_eq!(bits("MANAGE_SERVER"), bits("MANAGE_GUILD"));
+        assert!(bits("ADMINISTRATOR").is_some());
+        assert!(bits("ADMINS").is_none());
+    }
+
+    #[test]
+    fn permission_bits() {
+        let bits = |s| Permissions::from_str(s).map(|p| p.0);
+
+        // Mirrors the flags of `serenity::model::permissions::Permissions`.
+        assert_eq!(bits("PRIORITY_SPEAKER"), Some(1 << 8));
+        assert_eq!(bits("STREAM"), Some(1 << 9));
+        assert_eq!(bits("READ_MESSAGES"), Some(1 << 10));
+        assert_eq!(bits("MANAGE_EMOJIS"), Some(1 << 30));
+
+        // Not a flag of serenity's `Permissions`, which drops unknown bits when deserialising.
+        assert!(bits("MODERATE_MEMBERS").is_none());
+    }
+
+    #[test]
+    fn command_refs() {
+        let attr: Attribute = parse_quote!(#[commands(#[cfg(feature = "x")] foo, "bar", r#match)]);
+        let refs = CommandRef::parse_list(&attr).unwrap();
+
+        assert_eq!(refs.len(), 3);
+        assert_eq!(refs[0].cfgs.len(), 1);
+        assert_eq!(refs[0].name, "foo");
+        assert!(refs[1].cfgs.is_empty());
+        assert_eq!(refs[1].name, "bar");
+        assert_eq!(refs[2].name.unraw(), "match");
+
+        let attr: Attribute = parse_quote!(#[commands(#[inline] foo)]);
+        let err = CommandRef::parse_list(&attr).unwrap_err();
+        assert_eq!(err.to_string(), "only `#[cfg]` may be attached to a command");
+
+        let attr: Attribute = parse_quote!(#[commands()]);
+        assert!(CommandRef::parse_list(&attr).is_err());
+    }
+
+    #[test]
+    fn required_description() {
+        let name: syn::Ident = parse_quote!(ping);
+
+        let mut options = Options::new();
+        let err = options.require_description(&name).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "the command `ping` is listed in the help but has no description; add doc comments or a `#[description]`"
+        );
+
+        options.description.push(DescriptionPart::Text(" ".to_string()));
+        assert!(options.require_description(&name).is_err());
+
+        options.description.push(DescriptionPart::Text("Pong!".to_string()));
+        assert!(options.require_description(&name).is_ok());
+
+        let mut hidden = Options::new();
+        hidden.help_available = false;
+        assert!(hidden.require_description(&name).is_ok());
+    }
+
+    #[test]
+    fn group_modules() {
+        let group: GroupStruct = parse_quote! {
+            #[prefix = "math"]
+            mod math {
+                use super::*;
+
+                #[command]
+                async fn add() {}
+
+                #[cfg(feature = "x")]
+                #[serenity::framework::standard::macros::command]
+                async fn sub() {}
+
+                fn helper() {}
+
+                mod nested {
+                    #[command]
+                    async fn hidden() {}
+                }
+            }
+        };
+
+        let refs = group.module_commands();
+        assert_eq!(refs.len(), 2);
+        assert_eq!(refs[0].name, "add");
+        assert!(refs[0].cfgs.is_empty());
+        assert_eq!(refs[1].name, "sub");
+        assert_eq!(refs[1].cfgs.len(), 1);
+        assert!(refs.iter().all(|r| r.module.as_ref().map(|m| m == "math") == Some(true)));
+
+        let group: GroupStruct = parse_quote!(struct Math;);
+        assert!(group.module_commands().is_empty());
+
+        let err = syn::parse_str::<GroupStruct>("mod math;").unwrap_err();
+        assert_eq!(err.to_string(), "a group module must have a body for its commands to be discovered");
+    }
+
+    #[test]
+    fn help_permission_options() {
+        let options = HelpOptions::default();
+        assert!(!options.show_permissions);
+        assert_eq!(options.permissions_label, "Required permissions");
+
+        let attr: Attribute = parse_quote!(#[show_permissions]);
+        assert!(parse::<bool>(parse_values(&attr).unwrap()).unwrap());
+
+        let attr: Attribute = parse_quote!(#[permissions_label = "Needs"]);
+        assert_eq!(parse::<String>(parse_values(&attr).unwrap()).unwrap(), "Needs");
+    }
+
+    #[test]
+    fn option_assertions() {
+        let assertions: OptionAssertions = syn::parse_str(
+            "FOO_COMMAND_OPTIONS, min_args = 1, max_args = None, usage = Some(\"<a>\"), owners_only = true,",
+        )
+        .unwrap();
+
+        assert_eq!(assertions.expected.len(), 4);
+        assert_eq!(
+            assertions.into_token_stream().to_string(),
+            quote! {
+                const _: () = {
+                    const _: &serenity::framework::standard::CommandOptions = &FOO_COMMAND_OPTIONS;
+
+                    #[allow(non_upper_case_globals)]
+                    #[deny(unreachable_patterns)]
+                    const min_args: bool = match FOO_COMMAND_OPTIONS.min_args {
+                        Some(value) => match value { 1 => true, _ => false },
+                        _ => false
+                    };
+                    serenity::static_assertions::const_assert!(min_args);
+
+                    #[allow(non_upper_case_globals)]
+                    #[deny(unreachable_patterns)]
+                    const max_args: bool = match FOO_COMMAND_OPTIONS.max_args { None => true, _ => false };
+                    serenity::static_assertions::const_assert!(max_args);
+
+                    #[allow(non_upper_case_globals)]
+                    #[deny(unreachable_patterns)]
+                    const usage: bool = match FOO_COMMAND_OPTIONS.usage {
+                        Some(value) => match value.as_bytes() { b"<a>" => true, _ => false },
+                        _ => false
+                    };
+                    serenity::static_assertions::const_assert!(usage);
+
+                    #[allow(non_upper_case_globals)]
+                    #[deny(unreachable_patterns)]
+                    const owners_only: bool = match FOO_COMMAND_OPTIONS.owners_only { true => true, _ => false };
+                    serenity::static_assertions::const_assert!(owners_only);
+                };
+            }
+            .to_string()
+        );
+
+        let err = syn::parse_str::<OptionAssertions>("FOO_COMMAND_OPTIONS, typing = true, typing = false").unwrap_err();
+        assert_eq!(err.to_string(), "`typing` is asserted more than once");
+
+        let err = syn::parse_str::<OptionAssertions>("FOO_COMMAND_OPTIONS").unwrap_err();
+        assert_eq!(err.to_string(), "expected at least one `field = value` to assert");
+
+        assert!(syn::parse_str::<OptionAssertions>("FOO_COMMAND_OPTIONS, typing").is_err());
+    }
+
+    #[test]
+    fn optional_command_fields() {
+        // The fields are listed by hand, so make sure they follow `CommandOptions`.
+        let file = syn::parse_file(include_str!("../../src/framework/standard/structures/mod.rs")).unwrap();
+
+        let options = file
+            .items
+            .iter()
+            .find_map(|item| match item {
+                Item::Struct(s) if s.ident == "CommandOptions" => Some(s),
+                _ => None,
+            })
+            .unwrap();
+
+        let mut optional = options
+            .fields
+            .iter()
+            .filter(|field| match &field.ty {
+                Type::Path(ty) => matches!(ty.path.segments.last(), Some(s) if s.ident == "Option"),
+                _ => false,
+            })
+            .map(|field| field.ident.as_ref().unwrap().to_string())
+            .collect::<Vec<_>>();
+        optional.sort();
+
+        let mut listed = OPTIONAL_COMMAND_FIELDS.to_vec();
+        listed.sort_unstable();
+
+        assert_eq!(optional, listed);
+    }
+
+    #[test]
+    fn custom_static_base() {
+        let fun_name: syn::Ident = parse_quote!(generated_0042);
+
+        let args = CommandArgs::default();
+        assert_eq!(args.static_base(&fun_name).unwrap(), "generated_0042");
+
+        let mut args = CommandArgs {
+            fn_static: AsOption(Some("custom".to_string())),
+            ..CommandArgs::default()
+        };
+        let base = args.static_base(&fun_name).unwrap();
+        assert_eq!(name_with_case(&base, COMMAND, StaticCase::Screaming), "CUSTOM_COMMAND");
+        assert_eq!(name_with_case(&base, COMMAND_OPTIONS, StaticCase::Pascal), "CustomCommandOptions");
+
+        args.fn_static = AsOption(Some(" ".to_string()));
+        let err = args.static_base(&fun_name).unwrap_err();
+        assert_eq!(err.to_string(), "`fn_static` cannot be empty");
+    }
+
+    #[test]
+    fn doc_comments_are_flagged() {
+        let fun: CommandFun = syn::parse_str(
+            "/// Pings.\n#[cfg(test)] #[description(\"Pongs.\")] #[doc = \"Replies.\"] async fn ping() -> CommandResult { Ok(()) }",
+        )
+        .unwrap();
+
+        assert_eq!(fun.attributes.len(), 3);
+        assert!(fun.attributes.iter().all(|a| a.path.is_ident("description")));
+        assert_eq!(fun.doc_comments, [true, false, true]);
+    }
+
+    #[test]
+    fn required_data() {
+        let data: RequiredData = syn::parse_str("MyConfig").unwrap();
+        assert_eq!(data.binding, "my_config");
+
+        let data: RequiredData = syn::parse_str("crate::state::ShardManager").unwrap();
+        assert_eq!(data.binding, "shard_manager");
+
+        let data: RequiredData = syn::parse_str("config: r#MyConfig").unwrap();
+        assert_eq!(data.binding, "config");
+        assert_eq!(data.key.into_token_stream().to_string(), "r#MyConfig");
+
+        assert!(syn::parse_str::<RequiredData>("config:").is_err());
+    }
+
+    #[test]
+    fn required_data_fetch() {
+        let data: RequiredData = syn::parse_str("state::MyConfig").unwrap();
+        let fetch = data.fetch(&parse_quote!(ctx));
+
+        let expected = quote! {
+            let my_config = {
+                let data = ctx.data.read().await;
+
+                match data.get::<state::MyConfig>() {
+                    ::std::option::Option::Some(value) => ::std::clone::Clone::clone(value),
+                    ::std::option::Option::None => {
+                        return ::std::result::Result::Err(serenity::framework::standard::CommandError::from(
+                            "`state::MyConfig` is missing from the context's data"
+                        ));
+                    },
+                }
+            };
+        };
+
+        assert_eq!(fetch.into_token_stream().to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn quotes_option() {
+        assert_eq!(Options::new().quotes.0, None);
+
+        let attr: Attribute = parse_quote!(#[quotes("`", '\'')]);
+        let quotes = parse::<AsOption<Vec<char>>>(parse_values(&attr).unwrap()).unwrap();
+        assert_eq!(quotes.0, Some(vec!['`', '\'']));
+
+        let attr: Attribute = parse_quote!(#[quotes("`", "```")]);
+        let err = parse::<AsOption<Vec<char>>>(parse_values(&attr).unwrap()).unwrap_err();
+        assert_eq!(err.to_string(), "expected a single character");
+
+        let attr: Attribute = parse_quote!(#[quotes("`", 1)]);
+        let err = parse::<AsOption<Vec<char>>>(parse_values(&attr).unwrap()).unwrap_err();
+        assert_eq!(err.to_string(), "expected character, found integer literal");
+    }
+
+    #[test]
+    fn timeout_option() {
+        assert_eq!(Options::new().timeout.0, None);
+
+        let attr: Attribute = parse_quote!(#[timeout(30)]);
+        let timeout = parse::<AsOption<u64>>(parse_values(&attr).unwrap()).unwrap();
+        assert_eq!(timeout.into_token_stream().to_string(), "Some (30u64)");
+
+        let attr: Attribute = parse_quote!(#[timeout("soon")]);
+        let err = parse::<AsOption<u64>>(parse_values(&attr).unwrap()).unwrap_err();
+        assert_eq!(err.to_string(), "invalid integer");
+    }
+
+    #[test]
+    fn group_bucket() {
+        assert_eq!(GroupOptions::new().bucket.0, None);
+
+        let attr: Attribute = parse_quote!(#[bucket = "api"]);
+        let bucket = parse::<AsOption<String>>(parse_values(&attr).unwrap()).unwrap();
+        assert_eq!(bucket.0.as_deref(), Some("api"));
+    }
+
+    #[test]
+    fn feature_gates() {
+        assert!(CommandArgs::default().feature_gate().unwrap().is_none());
+
+        let args = CommandArgs {
+            feature: AsOption(Some("music".to_string())),
+            ..CommandArgs::default()
+        };
+        let (feature, gate) = args.feature_gate().unwrap().unwrap();
+        let expected: Attribute = parse_quote!(#[cfg(feature = "music")]);
+
+        assert_eq!(gate, expected);
+        assert!(super::is_static_compatible(&gate));
+        assert_eq!(AsOption(Some(feature)).into_token_stream().to_string(), "Some (\"music\")");
+
+        let args = CommandArgs {
+            feature: AsOption(Some(String::new())),
+            ..CommandArgs::default()
+        };
+        let err = args.feature_gate().unwrap_err();
+        assert_eq!(err.to_string(), "`feature` cannot be empty");
+    }
+
+    #[test]
+    fn only_in_values() {
+        let span = Span::call_site();
+
+        assert_eq!(OnlyIn::from_str("guilds", span).unwrap(), OnlyIn::Guild);
+        assert_eq!(OnlyIn::from_str("dm", span).unwrap(), OnlyIn::Dm);
+        assert_eq!(OnlyIn::from_str("both", span).unwrap(), OnlyIn::Both);
+        assert_eq!(OnlyIn::from_str("inherit", span).unwrap(), OnlyIn::None);
+        assert!(OnlyIn::from_str("everywhere", span).is_err());
+    }
+
+    #[test]
+    fn help_text_known_tokens() {
+        assert!(HelpText::validate("Use {prefix}{command} <command>", Span::call_site()).is_ok());
+        assert!(HelpText::validate("No tokens at all", Span::call_site()).is_ok());
+    }
+
+    #[test]
+    fn help_text_unknown_tokens() {
+        let err = HelpText::validate("Use {prefx}help", Span::call_site()).unwrap_err();
+        assert!(err.to_string().contains("`{prefx}`"));
+
+        assert!(HelpText::validate("Use {}", Span::call_site()).is_err());
+        assert!(HelpText::validate("Use {prefix", Span::call_site()).is_err());
+    }
+
+    #[test]
+    fn on_error_option() {
+        assert_eq!(Options::new().on_error.0, None);
+
+        let attr: Attribute = parse_quote!(#[on_error(handle_error)]);
+        let handler = parse::<AsOption<Ident>>(parse_values(&attr).unwrap()).unwrap();
+        assert_eq!(handler.into_token_stream().to_string(), "Some (handle_error)");
+
+        let attr: Attribute = parse_quote!(#[on_error(first, second)]);
+        assert!(parse::<AsOption<Ident>>(parse_values(&attr).unwrap()).is_err());
+    }
+
+    #[test]
+    fn num_delimiters_option() {
+        let attrs: Vec<Attribute> = vec![parse_quote!(#[num_delimiters(2)])];
+
+        let mut options = Options::new();
+        options.num_delimiters = parse(parse_values(&attrs[0]).unwrap()).unwrap();
+        assert_eq!(options.num_delimiters.to_token_stream().to_string(), "Some (2u16)");
+
+        let err = options.check_num_delimiters(&attrs).unwrap_err();
+        assert_eq!(err.to_string(), "`num_delimiters` requires the command to declare its own `delimiters`");
+
+        options.delimiters.push("|".to_string());
+        assert!(options.check_num_delimiters(&attrs).is_ok());
+    }
+
+    #[test]
+    fn self_sub_command() {
+        let name: Ident = parse_quote!(r#match);
+
+        let mut options = Options::new();
+        options.sub_commands = vec![parse_quote!(list), parse_quote!(add)];
+        assert!(options.reject_self_sub_command(&name).is_ok());
+
+        options.sub_commands.push(parse_quote!(r#match));
+        let err = options.reject_self_sub_command(&name).unwrap_err();
+        assert_eq!(err.to_string(), "a command cannot be its own sub command");
+
+        options.sub_commands = vec![parse_quote!(list)];
+        assert!(options.reject_self_sub_command(&parse_quote!(list)).is_err());
+    }
+
+    #[test]
+    fn category_option() {
+        assert_eq!(Options::new().category.0, None);
+
+        let attr: Attribute = parse_quote!(#[category("Moderation")]);
+        let category = parse::<AsOption<String>>(parse_values(&attr).unwrap()).unwrap();
+        assert_eq!(category.into_token_stream().to_string(), "Some (\"Moderation\")");
+
+        let attr: Attribute = parse_quote!(#[category = "Moderation"]);
+        let category = parse::<AsOption<String>>(parse_values(&attr).unwrap()).unwrap();
+        assert_eq!(category.0.as_deref(), Some("Moderation"));
+    }
+
+    #[test]
+    fn prefixes_option() {
+        assert!(Options::new().prefixes.is_empty());
+
+        let attrs: Vec<Attribute> = vec![parse_quote!(#[prefixes("!", "?")])];
+
+        let mut options = Options::new();
+        options.prefixes = parse(parse_values(&attrs[0]).unwrap()).unwrap();
+        assert_eq!(options.prefixes, ["!", "?"]);
+        assert!(options.check_prefixes(&attrs).is_ok());
+
+        let attrs: Vec<Attribute> = vec![parse_quote!(#[prefixes("!", "")])];
+        options.prefixes = parse(parse_values(&attrs[0]).unwrap()).unwrap();
+
+        let err = options.check_prefixes(&attrs).unwrap_err();
+        assert_eq!(err.to_string(), "a command's prefixes cannot be empty");
+    }
+
+    #[test]
+    fn deprecation_notice() {
+        assert_eq!(Options::new().deprecation_notice.0, None);
+
+        let mut options = Options::new();
+        let attr: Attribute = parse_quote!(#[deprecated_command("use bar instead")]);
+        options.deprecate(parse(parse_values(&attr).unwrap()).unwrap());
+        assert_eq!(options.deprecation_notice.into_token_stream().to_string(), "Some (\"use bar instead\")");
+
+        let mut options = Options::new();
+        let attr: Attribute = parse_quote!(#[deprecated_command]);
+        options.deprecate(parse(parse_values(&attr).unwrap()).unwrap());
+        assert_eq!(options.deprecation_notice.0.as_deref(), Some("This command is deprecated."));
+    }
+
+    #[test]
+    fn group_summary() {
+        assert_eq!(GroupOptions::new().summary.0, None);
+
+        let attr: Attribute = parse_quote!(#[summary = "Moderation tools."]);
+        let summary = parse::<AsOption<String>>(parse_values(&attr).unwrap()).unwrap();
+        assert_eq!(summary.0.as_deref(), Some("Moderation tools."));
+
+        let attr: Attribute = parse_quote!(#[summary("Moderation tools.")]);
+        let summary = parse::<AsOption<String>>(parse_values(&attr).unwrap()).unwrap();
+        assert_eq!(summary.into_token_stream().to_string(), "Some (\"Moderation tools.\")");
+    }
+
+    #[test]
+    fn base_min_args_option() {
+        assert!(Options::new().base_min_args.0.is_none());
+
+        let attr: Attribute = parse_quote!(#[base_min_args(2)]);
+        let bound = parse::<AsOption<ArgsBound>>(parse_values(&attr).unwrap()).unwrap();
+        assert_eq!(bound.into_token_stream().to_string(), "Some (2u16)");
+
+        let attr: Attribute = parse_quote!(#[base_min_args(MIN_ARGS)]);
+        let bound = parse::<AsOption<ArgsBound>>(parse_values(&attr).unwrap()).unwrap();
+        assert_eq!(bound.into_token_stream().to_string(), "Some (MIN_ARGS)");
+    }
+
+    #[test]
+    fn unknown_sub_option() {
+        assert!(Options::new().unknown_sub.0.is_none());
+
+        let attr: Attribute = parse_quote!(#[unknown_sub(not_found)]);
+        let mut options = Options::new();
+        options.unknown_sub = parse(parse_values(&attr).unwrap()).unwrap();
+        assert_eq!(options.unknown_sub.0.as_ref().unwrap(), "not_found");
+
+        let err = options.check_unknown_sub().unwrap_err();
+        assert_eq!(err.to_string(), "`unknown_sub` requires the command to have `sub_commands`");
+
+        options.sub_commands = vec![parse_quote!(list)];
+        assert!(options.check_unknown_sub().is_ok());
+    }
+
+    #[test]
+    fn name_constant() {
+        assert!(CommandArgs::default().name_constant("ping").is_empty());
+
+        let args = CommandArgs {
+            expose_name: true,
+            ..CommandArgs::default()
+        };
+        let expected = quote! {
+            #[allow(dead_code)]
+            const __COMMAND_NAME: &str = "ping";
+        };
+        assert_eq!(args.name_constant("ping").to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn collapsed_commands_text_option() {
+        let default = HelpOptions::default().collapsed_commands_text;
+        assert_eq!(
+            default.into_token_stream().to_string(),
+            "(\"*{count} hidden command*\" , \"*{count} hidden commands*\")"
+        );
+
+        let attr: Attribute = parse_quote!(#[collapsed_commands_text("{count} hidden", "{count} are hidden")]);
+        let text = parse::<CollapsedText>(parse_values(&attr).unwrap()).unwrap();
+        assert_eq!(text, CollapsedText("{count} hidden".to_string(), "{count} are hidden".to_string()));
+
+        let attr: Attribute = parse_quote!(#[collapsed_commands_text = "{count} hidden"]);
+        let text = parse::<CollapsedText>(parse_values(&attr).unwrap()).unwrap();
+        assert_eq!(text, CollapsedText("{count} hidden".to_string(), "{count} hidden".to_string()));
+
+        let attr: Attribute = parse_quote!(#[collapsed_commands_text("{prefix} hidden")]);
+        assert!(parse::<CollapsedText>(parse_values(&attr).unwrap()).is_err());
+
+        let attr: Attribute = parse_quote!(#[collapsed_commands_text("a", "b", "c")]);
+        assert!(parse::<CollapsedText>(parse_values(&attr).unwrap()).is_err());
+    }
+
+    #[test]
+    fn prefix_display_option() {
+        assert!(HelpOptions::default().prefix_display.0.is_empty());
+
+        let attr: Attribute = parse_quote!(#[prefix_display = "@Bot "]);
+        let prefix = parse::<HelpText>(parse_values(&attr).unwrap()).unwrap();
+        assert_eq!(prefix.into_token_stream().to_string(), "\"@Bot \"");
+
+        let attr: Attribute = parse_quote!(#[prefix_display("{prefix}")]);
+        assert_eq!(parse::<HelpText>(parse_values(&attr).unwrap()).unwrap().0, "{prefix}");
+
+        let attr: Attribute = parse_quote!(#[prefix_display("{bot}")]);
+        assert!(parse::<HelpText>(parse_values(&attr).unwrap()).is_err());
+    }
+}
@@ -1,16 +1,19 @@
-use crate::structures::CommandFun;
+use crate::attributes::ValueKind;
+use crate::structures::{CommandFun, StaticCase};
 use proc_macro::TokenStream;
 use proc_macro2::Span;
 use proc_macro2::TokenStream as TokenStream2;
-use quote::{format_ident, quote, ToTokens};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use std::collections::{HashMap, HashSet};
 use syn::{
     braced, bracketed, parenthesized,
+    ext::IdentExt,
     parse::{Error, Parse, ParseStream, Result as SynResult},
     parse_quote,
     punctuated::Punctuated,
     spanned::Spanned,
     token::{Comma, Mut},
-    Ident, Lifetime, Lit, Type,
+    GenericArgument, Ident, Lifetime, Lit, LitByteStr, PathArguments, Type,
 };
 
 pub trait LitExt {
@@ -42,24 +45,317 @@ impl LitExt for Lit {
 
     #[inline]
     fn to_ident(&self) -> Ident {
-        Ident::new(&self.to_str(), self.span())
+        let s = self.to_str();
+
+        // Raw identifiers keep their `r#` prefix when they pass through a string literal.
+        match s.get(..2) {
+            Some("r#") => Ident::new_raw(&s[2..], self.span()),
+            _ => Ident::new(&s, self.span()),
+        }
     }
 }
 
 pub trait IdentExt2: Sized {
     fn to_uppercase(&self) -> Self;
     fn with_suffix(&self, suf: &str) -> Ident;
+    fn with_cased_suffix(&self, suf: &str, case: StaticCase) -> Ident;
 }
 
 impl IdentExt2 for Ident {
     #[inline]
     fn to_uppercase(&self) -> Self {
-        format_ident!("{}", self.to_string().to_uppercase())
+        format_ident!("{}", sanitise(&self.unraw().to_string().to_uppercase()))
     }
 
     #[inline]
     fn with_suffix(&self, suffix: &str) -> Ident {
-        format_ident!("{}_{}", self.to_string().to_uppercase(), suffix)
+        name_with_suffix(&self.unraw().to_string(), suffix)
+    }
+
+    #[inline]
+    fn with_cased_suffix(&self, suffix: &str, case: StaticCase) -> Ident {
+        name_with_case(&self.unraw().to_string(), suffix, case)
+    }
+}
+
+/// Turns an arbitrary name, such as a command's, into an uppercase identifier ending in `suffix`.
+#[inline]
+pub fn name_with_suffix(name: &str, suffix: &str) -> Ident {
+    name_with_case(name, suffix, StaticCase::Screaming)
+}
+
+/// Turns an arbitrary name into an identifier ending in `suffix`, cased according to `case`.
+pub fn name_with_case(name: &str, suffix: &str, case: StaticCase) -> Ident {
+    let name = match case {
+        StaticCase::Screaming => format!("{}_{}", sanitise(&name.to_uppercase()), suffix),
+        StaticCase::Snake => format!("{}_{}", sanitise(&to_snake_case(name)), suffix.to_lowercase()),
+        StaticCase::Pascal => {
+            let words = format!("{}_{}", sanitise(&to_snake_case(name)), suffix.to_lowercase());
+            let mut res = String::with_capacity(words.len());
+
+            for word in words.split('_') {
+                let mut chars = word.chars();
+
+                if let Some(first) = chars.next() {
+                    res.extend(first.to_uppercase());
+                    res.push_str(chars.as_str());
+                }
+            }
+
+            // Dropping the underscores may leave a digit in front.
+            sanitise(&res)
+        },
+    };
+
+    format_ident!("{}", name)
+}
+
+/// Turns `s` into a valid identifier.
+///
+/// Uppercasing a non-ASCII identifier may yield characters that are not allowed in
+/// identifiers. Every such character is replaced with an underscore, and an underscore
+/// is prepended if the first character may not start an identifier.
+pub fn sanitise(s: &str) -> String {
+    let mut res = String::with_capacity(s.len());
+
+    for (i, c) in s.chars().enumerate() {
+        if i == 0 && c != '_' && !unicode_ident::is_xid_start(c) {
+            res.push('_');
+        }
+
+        if unicode_ident::is_xid_continue(c) {
+            res.push(c);
+        } else {
+            res.push('_');
+        }
+    }
+
+    res
+}
+
+/// Converts a `CamelCase` name to `snake_case`.
+pub fn to_snake_case(s: &str) -> String {
+    let mut res = String::with_capacity(s.len() + 4);
+
+    for (i, c) in s.chars().enumerate() {
+        if c.is_uppercase() {
+            if i != 0 && !res.ends_with('_') {
+                res.push('_');
+            }
+
+            res.extend(c.to_lowercase());
+        } else {
+            res.push(c);
+        }
+    }
+
+    res
+}
+
+/// Removes repeated elements from `v`, keeping the first occurrence of each.
+pub fn dedup<T: PartialEq>(v: &mut Vec<T>) {
+    let mut i = 0;
+
+    while i < v.len() {
+        if v[..i].contains(&v[i]) {
+            v.remove(i);
+        } else {
+            i += 1;
+        }
+    }
+}
+
+/// Whether `name` can be the name of a slash command: 1 to 32 lowercase
+/// letters, digits, `-` or `_`.
+pub fn is_slash_name(name: &str) -> bool {
+    let len = name.chars().count();
+
+    (1..=32).contains(&len)
+        && name
+            .chars()
+            .all(|c| c == '-' || c == '_' || (c.is_alphanumeric() && !c.is_uppercase()))
+}
+
+/// A part of a command's usage, as split by [`parse_usage`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum UsageToken {
+    /// Text outside of any brackets, such as a sub command's name.
+    Literal(String),
+    /// An argument in angle brackets, `<arg>`.
+    Required(String),
+    /// An argument in square brackets, `[arg]`.
+    Optional(String),
+}
+
+impl ToTokens for UsageToken {
+    fn to_tokens(&self, stream: &mut TokenStream2) {
+        let path = quote!(serenity::framework::standard::UsageToken);
+
+        stream.extend(match self {
+            UsageToken::Literal(s) => quote!(#path::Literal(#s)),
+            UsageToken::Required(s) => quote!(#path::Required(#s)),
+            UsageToken::Optional(s) => quote!(#path::Optional(#s)),
+        });
+    }
+}
+
+/// Splits `usage` into its words and its `<required>` and `[optional]` arguments.
+///
+/// Brackets may nest, in which case the outermost pair decides the kind of the
+/// argument. Unbalanced brackets do not fail the parse: a stray closing bracket
+/// is kept as text and an unclosed argument is kept as literal words. Either
+/// case is reported by the returned warning.
+pub fn parse_usage(usage: &str) -> (Vec<UsageToken>, Option<String>) {
+    fn words(tokens: &mut Vec<UsageToken>, s: &str) {
+        tokens.extend(s.split_whitespace().map(|w| UsageToken::Literal(w.to_string())));
+    }
+
+    let mut tokens = Vec::new();
+    let mut warning = None;
+    let mut closers = Vec::new();
+    let mut start = 0;
+
+    for (i, c) in usage.char_indices() {
+        match c {
+            '<' | '[' => {
+                if closers.is_empty() {
+                    words(&mut tokens, &usage[start..i]);
+                    start = i;
+                }
+
+                closers.push(if c == '<' { '>' } else { ']' });
+            },
+            '>' | ']' if closers.last() == Some(&c) => {
+                closers.pop();
+
+                if closers.is_empty() {
+                    let arg = usage[start + 1..i].trim().to_string();
+
+                    tokens.push(if c == '>' {
+                        UsageToken::Required(arg)
+                    } else {
+                        UsageToken::Optional(arg)
+                    });
+                    start = i + 1;
+                }
+            },
+            '>' | ']' => {
+                warning.get_or_insert_with(|| format!("`usage` has an unmatched `{}`", c));
+            },
+            _ => {},
+        }
+    }
+
+    if !closers.is_empty() {
+        let opener = &usage[start..=start];
+        warning.get_or_insert_with(|| format!("`usage` has an unclosed `{}`", opener));
+    }
+
+    words(&mut tokens, &usage[start..]);
+
+    (tokens, warning)
+}
+
+/// Emits `message` as a compiler warning pointing at `span`.
+///
+/// Procedural macros cannot raise warnings on stable Rust, so this goes through
+/// the `deprecated` lint of a throwaway item.
+pub fn warning(message: &str, span: Span) -> TokenStream2 {
+    quote_spanned! {span=>
+        const _: () = {
+            #[deprecated(note = #message)]
+            struct Warning;
+
+            let _ = Warning;
+        };
+    }
+}
+
+/// Names of the options applied so far, used to reject options that are applied twice.
+#[derive(Debug, Default)]
+pub struct SeenOptions(HashSet<String>);
+
+impl SeenOptions {
+    /// Records `name`, failing at `span` if it was already recorded.
+    pub fn insert(&mut self, name: &str, span: Span) -> SynResult<()> {
+        if !self.0.insert(name.to_string()) {
+            return Err(Error::new(
+                span,
+                format_args!("`{}` is specified more than once", name),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// The forms options were written in so far, used to reject an option written both as
+/// `#[name = value]` and `#[name(value)]`.
+#[derive(Debug, Default)]
+pub struct OptionForms(HashMap<String, (bool, Span)>);
+
+impl OptionForms {
+    /// Records the form of `name`, failing at both `span` and the earlier application
+    /// if it was already written in the other form. Bare names, `#[name]`, are not recorded.
+    pub fn insert(&mut self, name: &str, kind: ValueKind, span: Span) -> SynResult<()> {
+        let equals = match kind {
+            ValueKind::Name => return Ok(()),
+            ValueKind::Equals => true,
+            ValueKind::List | ValueKind::SingleList => false,
+        };
+
+        match self.0.get(name) {
+            Some(&(first, first_span)) if first != equals => {
+                let mut err = Error::new(
+                    span,
+                    format_args!("`{0}` is written both as `#[{0} = ...]` and `#[{0}(...)]`; use one form", name),
+                );
+                err.combine(Error::new(first_span, format_args!("`{}` is first written here", name)));
+
+                Err(err)
+            },
+            Some(_) => Ok(()),
+            None => {
+                self.0.insert(name.to_string(), (equals, span));
+
+                Ok(())
+            },
+        }
+    }
+}
+
+/// Generates `pub static #lookup`, pairing each of `names` with the `command` static.
+///
+/// The entries are deduplicated and sorted by name, so the slice can be searched with
+/// `binary_search_by_key`.
+pub fn lookup_table(lookup: &Ident, command: &Ident, names: &[&str]) -> TokenStream2 {
+    let mut names = names.to_vec();
+    names.sort_unstable();
+    names.dedup();
+
+    let command_path = quote!(serenity::framework::standard::Command);
+    let entries = names.into_iter().map(|name| quote!((#name, &#command)));
+
+    quote! {
+        pub static #lookup: &[(&str, &#command_path)] = &[#(#entries),*];
+    }
+}
+
+/// Generates `pub const fn #fun(name: &str) -> bool`, telling whether `name` is one of `names`.
+///
+/// `str`s cannot be compared in a `const fn`, so their bytes are matched instead.
+pub fn name_matcher(fun: &Ident, names: &[&str]) -> TokenStream2 {
+    let names = names.iter().map(|name| LitByteStr::new(name.as_bytes(), Span::call_site()));
+
+    quote! {
+        #[doc = "Whether `name` is the command's name or one of its aliases."]
+        #[allow(dead_code)]
+        pub const fn #fun(name: &str) -> bool {
+            match name.as_bytes() {
+                #(#names)|* => true,
+                _ => false,
+            }
+        }
     }
 }
 
@@ -160,11 +456,18 @@ impl ToTokens for Argument {
     }
 }
 
+/// Generates an assertion that `have` and `expect` are the same type.
+///
+/// The assertion is spanned at `have`, so that a mismatch is reported at the offending type
+/// rather than at the macro's invocation.
 #[inline]
 pub fn generate_type_validation(have: Type, expect: Type) -> syn::Stmt {
-    parse_quote! {
+    let span = have.span();
+
+    syn::parse2(quote_spanned! {span=>
         serenity::static_assertions::assert_type_eq_all!(#have, #expect);
-    }
+    })
+    .unwrap()
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -174,6 +477,19 @@ pub enum DeclarFor {
     Check,
 }
 
+impl DeclarFor {
+    /// The signature the function is expected to have, as written in diagnostics.
+    pub fn signature(self) -> &'static str {
+        match self {
+            DeclarFor::Command => "async fn(&Context, &Message, Args) -> CommandResult",
+            DeclarFor::Help => {
+                "async fn(&Context, &Message, Args, &'static HelpOptions, &[&'static CommandGroup], HashSet<UserId>) -> CommandResult"
+            }
+            DeclarFor::Check => "async fn(&Context, &Message, &mut Args, &CommandOptions) -> CheckResult",
+        }
+    }
+}
+
 pub fn create_declaration_validations(fun: &mut CommandFun, dec_for: DeclarFor) -> SynResult<()> {
     let len = match dec_for {
         DeclarFor::Command => 3,
@@ -183,8 +499,12 @@ pub fn create_declaration_validations(fun: &mut CommandFun, dec_for: DeclarFor)
 
     if fun.args.len() > len {
         return Err(Error::new(
-            fun.args.last().unwrap().span(),
-            format_args!("function's arity exceeds more than {} arguments", len),
+            fun.args[len].span(),
+            format_args!(
+                "function's arity exceeds more than {} arguments; expected `{}`",
+                len,
+                dec_for.signature()
+            ),
         ));
     }
 
@@ -239,6 +559,63 @@ pub fn create_return_type_validation(r#fn: &mut CommandFun, expect: Type) {
     r#fn.body.insert(0, stmt);
 }
 
+/// Returns `E` if `ret` is written as `Result<(), E>`.
+///
+/// Commands may return such a result instead of a `CommandResult`, as long as `E`
+/// converts into a `CommandError`.
+pub fn result_error_type(ret: &Type) -> Option<&Type> {
+    let path = match ret {
+        Type::Path(path) if path.qself.is_none() => &path.path,
+        _ => return None,
+    };
+
+    let segment = path.segments.last()?;
+
+    if segment.ident != "Result" {
+        return None;
+    }
+
+    let args = match &segment.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 2 => &args.args,
+        _ => return None,
+    };
+
+    match (&args[0], &args[1]) {
+        (GenericArgument::Type(Type::Tuple(unit)), GenericArgument::Type(err)) if unit.elems.is_empty() => Some(err),
+        _ => None,
+    }
+}
+
+/// Validates the return type of a command.
+///
+/// A `CommandResult` is returned as is. For a `Result<(), E>`, the body is wrapped so that
+/// its error is converted into a `CommandError`, and the function's return type becomes a `CommandResult`.
+pub fn create_command_return_validation(fun: &mut CommandFun) {
+    let command_result: Type = parse_quote!(serenity::framework::standard::CommandResult);
+
+    let err = match result_error_type(&fun.ret) {
+        Some(err) => err.clone(),
+        None => return create_return_type_validation(fun, command_result),
+    };
+
+    let ret = &fun.ret;
+    let body = &fun.body;
+
+    // The conversion is spanned at the error type, so that an error lacking it is reported there.
+    let convert = quote_spanned! {err.span()=>
+        <serenity::framework::standard::CommandError as ::std::convert::From<#err>>::from
+    };
+
+    let block: syn::Block = parse_quote!({
+        let result: #ret = async move { #(#body)* }.await;
+
+        result.map_err(#convert)
+    });
+
+    fun.ret = command_result;
+    fun.body = block.stmts;
+}
+
 #[inline]
 pub fn populate_fut_lifetimes_on_refs(args: &mut Vec<Argument>) {
     for arg in args {
@@ -247,3 +624,271 @@ pub fn populate_fut_lifetimes_on_refs(args: &mut Vec<Argument>) {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{create_declaration_validations, create_return_type_validation, dedup, is_slash_name, lookup_table, name_with_case, name_with_suffix, parse_usage, name_matcher, result_error_type, sanitise, to_snake_case, DeclarFor, IdentExt2, LitExt, OptionForms, SeenOptions, UsageToken};
+    use crate::attributes::parse_values;
+    use crate::consts::{COMMAND, GROUP_OPTIONS};
+    use crate::structures::CommandFun;
+    use proc_macro2::Span;
+    use quote::{quote, ToTokens};
+    use syn::{parse_quote, Attribute, Ident, Lit, LitStr};
+
+    #[test]
+    fn help_arity_states_signature() {
+        let mut fun: CommandFun = syn::parse_str(
+            "async fn my_help(ctx: &Context, msg: &Message, args: Args, options: &'static HelpOptions, \
+             groups: &[&'static CommandGroup], owners: HashSet<UserId>, extra: u8) -> CommandResult { Ok(()) }",
+        )
+        .unwrap();
+
+        let err = create_declaration_validations(&mut fun, DeclarFor::Help).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "function's arity exceeds more than 6 arguments; expected \
+             `async fn(&Context, &Message, Args, &'static HelpOptions, &[&'static CommandGroup], HashSet<UserId>) -> CommandResult`"
+        );
+    }
+
+    #[test]
+    fn type_validations_span_the_checked_type() {
+        let mut fun: CommandFun = syn::parse_str(
+            "async fn ping(ctx: &Context,\n    msg: &str) -> WrongType {\n    Ok(())\n}",
+        )
+        .unwrap();
+
+        let start = |stmt: &syn::Stmt| {
+            let token = stmt.to_token_stream().into_iter().next().unwrap();
+            let start = token.span().start();
+
+            (start.line, start.column)
+        };
+
+        create_return_type_validation(&mut fun, parse_quote!(CommandResult));
+        assert_eq!(start(&fun.body[0]), (2, 18));
+
+        // Each validation is inserted ahead of the previous ones.
+        create_declaration_validations(&mut fun, DeclarFor::Command).unwrap();
+        assert_eq!(start(&fun.body[0]), (2, 9));
+        assert_eq!(start(&fun.body[1]), (1, 19));
+        assert_eq!(start(&fun.body[2]), (2, 18));
+    }
+
+    #[test]
+    fn usage_tokens() {
+        use UsageToken::*;
+
+        let lit = |s: &str| Literal(s.to_string());
+        let req = |s: &str| Required(s.to_string());
+        let opt = |s: &str| Optional(s.to_string());
+
+        assert_eq!(parse_usage(""), (vec![], None));
+        assert_eq!(parse_usage("<user> [reason]"), (vec![req("user"), opt("reason")], None));
+        assert_eq!(
+            parse_usage("add <first name>  [ tags... ]"),
+            (vec![lit("add"), req("first name"), opt("tags...")], None)
+        );
+        assert_eq!(parse_usage("[<a> <b>]<c>"), (vec![opt("<a> <b>"), req("c")], None));
+        assert_eq!(
+            parse_usage("<user]> x"),
+            (vec![req("user]"), lit("x")], Some("`usage` has an unmatched `]`".to_string()))
+        );
+        assert_eq!(
+            parse_usage("set <key> [value"),
+            (vec![lit("set"), req("key"), lit("[value")], Some("`usage` has an unclosed `[`".to_string()))
+        );
+        assert_eq!(
+            parse_usage("a> <b>"),
+            (vec![lit("a>"), req("b")], Some("`usage` has an unmatched `>`".to_string()))
+        );
+    }
+
+    #[test]
+    fn with_suffix_strips_raw_prefix() {
+        let ident: Ident = syn::parse_str("r#match").unwrap();
+
+        assert_eq!(ident.with_suffix(COMMAND).to_string(), "MATCH_COMMAND");
+        assert_eq!(ident.to_uppercase().to_string(), "MATCH");
+    }
+
+    #[test]
+    fn raw_literal_to_ident() {
+        let lit = Lit::Str(LitStr::new("r#match", Span::call_site()));
+
+        assert_eq!(lit.to_ident().with_suffix(COMMAND).to_string(), "MATCH_COMMAND");
+    }
+
+    #[test]
+    fn sanitise_unicode() {
+        assert_eq!(sanitise("CAFÉ"), "CAFÉ");
+        assert_eq!(sanitise("PING🏓"), "PING_");
+        assert_eq!(sanitise("٣D"), "_٣D");
+        assert_eq!(sanitise("_X"), "_X");
+    }
+
+    #[test]
+    fn with_suffix_accented() {
+        let ident: Ident = syn::parse_str("café").unwrap();
+
+        assert_eq!(ident.with_suffix(COMMAND).to_string(), "CAFÉ_COMMAND");
+    }
+
+    #[test]
+    fn name_with_case_modes() {
+        use crate::structures::StaticCase::*;
+
+        assert_eq!(name_with_case("foo_bar", COMMAND, Screaming).to_string(), "FOO_BAR_COMMAND");
+        assert_eq!(name_with_case("foo_bar", COMMAND, Pascal).to_string(), "FooBarCommand");
+        assert_eq!(name_with_case("foo_bar", COMMAND, Snake).to_string(), "foo_bar_command");
+
+        assert_eq!(name_with_case("BananaPhone", GROUP_OPTIONS, Screaming).to_string(), "BANANAPHONE_GROUP_OPTIONS");
+        assert_eq!(name_with_case("BananaPhone", GROUP_OPTIONS, Pascal).to_string(), "BananaPhoneGroupOptions");
+        assert_eq!(name_with_case("BananaPhone", GROUP_OPTIONS, Snake).to_string(), "banana_phone_group_options");
+
+        assert_eq!(name_with_case("1up", COMMAND, Pascal).to_string(), "_1upCommand");
+        assert_eq!(name_with_case("foo-bar", COMMAND, Pascal).to_string(), "FooBarCommand");
+    }
+
+    #[test]
+    fn name_with_suffix_sanitises() {
+        assert_eq!(name_with_suffix("bar", COMMAND).to_string(), "BAR_COMMAND");
+        assert_eq!(name_with_suffix("foo-bar", COMMAND).to_string(), "FOO_BAR_COMMAND");
+    }
+
+    #[test]
+    fn dedup_keeps_first_occurrence() {
+        let mut v = vec!["b", "a", "b", "c", "a"];
+        dedup(&mut v);
+
+        assert_eq!(v, ["b", "a", "c"]);
+    }
+
+    #[test]
+    fn result_error_types() {
+        let ret: syn::Type = syn::parse_str("Result<(), serenity::Error>").unwrap();
+        let err = result_error_type(&ret).unwrap();
+        assert_eq!(quote::quote!(#err).to_string(), "serenity :: Error");
+
+        let ret: syn::Type = syn::parse_str("std::result::Result<(), MyError>").unwrap();
+        assert!(result_error_type(&ret).is_some());
+
+        let ret: syn::Type = syn::parse_str("CommandResult").unwrap();
+        assert!(result_error_type(&ret).is_none());
+
+        let ret: syn::Type = syn::parse_str("Result<u8, MyError>").unwrap();
+        assert!(result_error_type(&ret).is_none());
+
+        let ret: syn::Type = syn::parse_str("anyhow::Result<()>").unwrap();
+        assert!(result_error_type(&ret).is_none());
+    }
+
+    #[test]
+    fn slash_names() {
+        assert!(is_slash_name("ping"));
+        assert!(is_slash_name("set-prefix_2"));
+        assert!(is_slash_name("café"));
+        assert!(is_slash_name(&"a".repeat(32)));
+
+        assert!(!is_slash_name(""));
+        assert!(!is_slash_name(&"a".repeat(33)));
+        assert!(!is_slash_name("Ping"));
+        assert!(!is_slash_name("set prefix"));
+        assert!(!is_slash_name("ping!"));
+    }
+
+    #[test]
+    fn seen_options() {
+        let mut seen = SeenOptions::default();
+
+        assert!(seen.insert("checks_label", Span::call_site()).is_ok());
+        assert!(seen.insert("aliases_label", Span::call_site()).is_ok());
+
+        let err = seen.insert("checks_label", Span::call_site()).unwrap_err();
+        assert_eq!(err.to_string(), "`checks_label` is specified more than once");
+    }
+
+    #[test]
+    fn name_matchers() {
+        let matcher = name_matcher(&Ident::new("ping_matches", Span::call_site()), &["ping", "p", "café"]);
+
+        let expected = quote! {
+            #[doc = "Whether `name` is the command's name or one of its aliases."]
+            #[allow(dead_code)]
+            pub const fn ping_matches(name: &str) -> bool {
+                match name.as_bytes() {
+                    b"ping" | b"p" | b"caf\xC3\xA9" => true,
+                    _ => false,
+                }
+            }
+        };
+
+        assert_eq!(matcher.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn lookup_tables() {
+        let lookup = Ident::new("BAN_COMMAND_LOOKUP", Span::call_site());
+        let command = Ident::new("BAN_COMMAND", Span::call_site());
+        let table = lookup_table(&lookup, &command, &["ban", "kick", "b", "ban", "Ban"]);
+
+        let expected = quote! {
+            pub static BAN_COMMAND_LOOKUP: &[(&str, &serenity::framework::standard::Command)] = &[
+                ("Ban", &BAN_COMMAND),
+                ("b", &BAN_COMMAND),
+                ("ban", &BAN_COMMAND),
+                ("kick", &BAN_COMMAND)
+            ];
+        };
+
+        assert_eq!(table.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn conflicting_forms() {
+        let attrs: Vec<Attribute> = vec![
+            parse_quote!(#[description = "Pings."]),
+            parse_quote!(#[description = "Replies with pong."]),
+            parse_quote!(#[usage("<user>")]),
+            parse_quote!(#[owners_only]),
+            parse_quote!(#[owners_only(true)]),
+            parse_quote!(#[description("Pongs.")]),
+        ];
+        let mut forms = OptionForms::default();
+        let mut results = attrs.iter().map(|attr| {
+            let values = parse_values(attr).unwrap();
+
+            forms.insert(&values.name.to_string(), values.kind, values.span)
+        });
+
+        for _ in 0..5 {
+            assert!(results.next().unwrap().is_ok());
+        }
+
+        let err = results.next().unwrap().unwrap_err();
+        let messages = err.into_iter().map(|e| e.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(
+            messages,
+            [
+                "`description` is written both as `#[description = ...]` and `#[description(...)]`; use one form",
+                "`description` is first written here",
+            ]
+        );
+    }
+
+    #[test]
+    fn snake_case() {
+        assert_eq!(to_snake_case("General"), "general");
+        assert_eq!(to_snake_case("BananaPhone"), "banana_phone");
+        assert_eq!(to_snake_case("Owner_Only"), "owner_only");
+        assert_eq!(to_snake_case("misc"), "misc");
+    }
+
+    #[test]
+    fn with_suffix_plain() {
+        let ident: Ident = syn::parse_str("ping").unwrap();
+
+        assert_eq!(ident.with_suffix(COMMAND).to_string(), "PING_COMMAND");
+    }
+}
@@ -87,6 +87,7 @@ impl<'a> From<&'a str> for Delimiter {
 enum TokenKind {
     Argument,
     QuotedArgument,
+    Delimiter,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -102,19 +103,24 @@ impl Token {
     }
 }
 
-fn lex(stream: &mut Stream<'_>, delims: &[Cow<'_, str>]) -> Option<Token> {
+/// The characters surrounding quoted arguments, unless others are given.
+const DEFAULT_QUOTES: &[char] = &['"'];
+
+fn lex(stream: &mut Stream<'_>, delims: &[Cow<'_, str>], escape: Option<char>, quotes: &[char]) -> Option<Token> {
     if stream.is_empty() {
         return None;
     }
 
     let start = stream.offset();
-    if stream.current()? == b'"' {
-        stream.next();
+    if let Some(quote) = stream.current_char().filter(|c| quotes.contains(c)) {
+        stream.next_char();
 
-        stream.take_until(|b| b == b'"');
+        while stream.current_char().map_or(false, |c| c != quote) {
+            stream.next_char();
+        }
 
-        let is_quote = stream.current().map_or(false, |b| b == b'"');
-        stream.next();
+        let is_quote = stream.current_char() == Some(quote);
+        stream.next_char();
 
         let end = stream.offset();
 
@@ -134,6 +140,15 @@ fn lex(stream: &mut Stream<'_>, delims: &[Cow<'_, str>]) -> Option<Token> {
     let mut end = start;
 
     'outer: while !stream.is_empty() {
+        // An escaped character can never start a delimiter.
+        if escape.is_some() && stream.current_char() == escape {
+            stream.next_char();
+            stream.next_char();
+            end = stream.offset();
+
+            continue;
+        }
+
         for delim in delims {
             end = stream.offset();
 
@@ -149,12 +164,73 @@ fn lex(stream: &mut Stream<'_>, delims: &[Cow<'_, str>]) -> Option<Token> {
     Some(Token::new(TokenKind::Argument, start, end))
 }
 
-fn remove_quotes(s: &str) -> &str {
-    if s.starts_with('"') && s.ends_with('"') {
-        return &s[1..s.len() - 1];
+/// Removes `escape` from unquoted arguments, keeping the characters it escaped.
+/// The spans of `tokens` are updated to point into the returned string.
+fn unescape(message: &str, tokens: &mut [Token], escape: char) -> String {
+    let mut res = String::with_capacity(message.len());
+    let mut last = 0;
+
+    for token in tokens {
+        let (start, end) = token.span;
+
+        res.push_str(&message[last..start]);
+        let new_start = res.len();
+
+        if token.kind == TokenKind::Argument {
+            let mut chars = message[start..end].chars();
+
+            while let Some(c) = chars.next() {
+                if c == escape {
+                    res.extend(chars.next());
+                } else {
+                    res.push(c);
+                }
+            }
+        } else {
+            res.push_str(&message[start..end]);
+        }
+
+        token.span = (new_start, res.len());
+        last = end;
+    }
+
+    res.push_str(&message[last..]);
+
+    res
+}
+
+/// Counts the delimiters between the arguments of `message`, lexed the same way as by
+/// [`Args::with_quotes`]. Delimiters inside quoted arguments and escaped ones are not counted.
+///
+/// [`Args::with_quotes`]: struct.Args.html#method.with_quotes
+pub(crate) fn count_delimiters(
+    message: &str,
+    possible_delimiters: &[Delimiter],
+    escape: Option<char>,
+    quotes: &[char],
+) -> usize {
+    Args::tokenise_with_delimiters(message, possible_delimiters, escape, quotes)
+        .iter()
+        .filter(|token| token.kind == TokenKind::Delimiter)
+        .count()
+}
+
+/// The quote surrounding `s`, if it starts and ends with the same one of `quotes`.
+fn surrounding_quote(s: &str, quotes: &[char]) -> Option<char> {
+    let quote = s.chars().next().filter(|c| quotes.contains(c))?;
+
+    if s.len() >= 2 * quote.len_utf8() && s.ends_with(quote) {
+        Some(quote)
+    } else {
+        None
     }
+}
 
-    s
+fn remove_quotes<'a>(s: &'a str, quotes: &[char]) -> &'a str {
+    match surrounding_quote(s, quotes) {
+        Some(quote) => &s[quote.len_utf8()..s.len() - quote.len_utf8()],
+        None => s,
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -257,7 +333,14 @@ enum State {
 #[derive(Clone, Debug)]
 pub struct Args {
     message: String,
+    /// The message without escape characters, if an escape character was given.
+    /// Tokens then point into this instead of `message`.
+    unescaped: Option<String>,
     args: Vec<Token>,
+    /// The characters surrounding quoted arguments.
+    quotes: Vec<char>,
+    /// The prefix and the name the help command was invoked with.
+    invocation: Option<(String, String)>,
     offset: usize,
     state: State,
 }
@@ -291,6 +374,87 @@ impl Args {
     ///
     /// [`Args`]: #struct.Args.html
     pub fn new(message: &str, possible_delimiters: &[Delimiter]) -> Self {
+        Self::with_quotes(message, possible_delimiters, None, DEFAULT_QUOTES)
+    }
+
+    /// Create a new instance of `Args`, where `escape` makes the character
+    /// following it literal in unquoted arguments. An escaped delimiter does
+    /// not split arguments. The escape character itself is removed from the
+    /// arguments, but kept in [`message`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serenity::framework::standard::{Args, Delimiter};
+    ///
+    /// let mut args = Args::new_escaped(r"a\,b,c", &[Delimiter::Single(',')], '\\');
+    ///
+    /// assert_eq!(args.single::<String>().unwrap(), "a,b");
+    /// assert_eq!(args.single::<String>().unwrap(), "c");
+    /// assert_eq!(args.message(), r"a\,b,c");
+    /// ```
+    ///
+    /// [`message`]: #method.message
+    pub fn new_escaped(message: &str, possible_delimiters: &[Delimiter], escape: char) -> Self {
+        Self::with_quotes(message, possible_delimiters, Some(escape), DEFAULT_QUOTES)
+    }
+
+    /// Create a new instance of `Args`, where an argument starting with one
+    /// of `quotes` is quoted up to the next occurrence of the same character,
+    /// instead of between `"`. An `escape`, if given, behaves as in
+    /// [`new_escaped`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serenity::framework::standard::{Args, Delimiter};
+    ///
+    /// let mut args = Args::with_quotes("`let x = 1;` 'a b' \"c", &[Delimiter::Single(' ')], None, &['`', '\'']);
+    ///
+    /// args.quoted();
+    /// assert_eq!(args.single::<String>().unwrap(), "let x = 1;");
+    /// assert_eq!(args.single::<String>().unwrap(), "a b");
+    /// // `"` is no longer a quote.
+    /// assert_eq!(args.single::<String>().unwrap(), "\"c");
+    /// ```
+    ///
+    /// [`new_escaped`]: #method.new_escaped
+    pub fn with_quotes(message: &str, possible_delimiters: &[Delimiter], escape: Option<char>, quotes: &[char]) -> Self {
+        let mut args = Self::tokenise(message, possible_delimiters, escape, quotes);
+        let unescaped = escape.map(|escape| unescape(message, &mut args, escape));
+
+        Args {
+            args,
+            message: message.to_string(),
+            unescaped,
+            quotes: quotes.to_vec(),
+            invocation: None,
+            offset: 0,
+            state: State::None,
+        }
+    }
+
+    /// Records the prefix and the name the help command was invoked with.
+    pub(crate) fn with_invocation(mut self, prefix: &str, name: &str) -> Self {
+        self.invocation = Some((prefix.to_string(), name.to_string()));
+
+        self
+    }
+
+    fn tokenise(message: &str, possible_delimiters: &[Delimiter], escape: Option<char>, quotes: &[char]) -> Vec<Token> {
+        let mut tokens = Self::tokenise_with_delimiters(message, possible_delimiters, escape, quotes);
+        tokens.retain(|token| token.kind != TokenKind::Delimiter);
+
+        tokens
+    }
+
+    /// Like `tokenise`, but also keeps a token for every delimiter between the arguments.
+    fn tokenise_with_delimiters(
+        message: &str,
+        possible_delimiters: &[Delimiter],
+        escape: Option<char>,
+        quotes: &[char],
+    ) -> Vec<Token> {
         let delims = possible_delimiters
             .iter()
             .filter(|d| match d {
@@ -300,8 +464,8 @@ impl Args {
             .map(|delim| delim.to_str())
             .collect::<Vec<_>>();
 
-        let args = if delims.is_empty() && !message.is_empty() {
-            let kind = if message.starts_with('"') && message.ends_with('"') {
+        if delims.is_empty() && !message.is_empty() {
+            let kind = if surrounding_quote(message, quotes).is_some() {
                 TokenKind::QuotedArgument
             } else {
                 TokenKind::Argument
@@ -313,18 +477,36 @@ impl Args {
             let mut args = Vec::new();
             let mut stream = Stream::new(message);
 
-            while let Some(token) = lex(&mut stream, &delims) {
+            while let Some(token) = lex(&mut stream, &delims, escape, quotes) {
+                // Whatever `lex` consumed past the argument are the delimiters following it.
+                let (mut start, end) = (token.span.1, stream.offset());
                 args.push(token);
+
+                while start < end {
+                    let delim = delims
+                        .iter()
+                        .find(|d| !d.is_empty() && message[start..end].starts_with(d.as_ref()));
+
+                    match delim {
+                        Some(delim) => {
+                            args.push(Token::new(TokenKind::Delimiter, start, start + delim.len()));
+                            start += delim.len();
+                        },
+                        None => break,
+                    }
+                }
             }
 
             args
-        };
+        }
+    }
 
-        Args {
-            args,
-            message: message.to_string(),
-            offset: 0,
-            state: State::None,
+    /// The text the arguments' spans point into.
+    #[inline]
+    fn text(&self) -> &str {
+        match &self.unescaped {
+            Some(unescaped) => unescaped,
+            None => &self.message,
         }
     }
 
@@ -337,7 +519,7 @@ impl Args {
     fn slice(&self) -> &str {
         let (start, end) = self.span();
 
-        &self.message[start..end]
+        &self.text()[start..end]
     }
 
     /// Move to the next argument.
@@ -391,18 +573,18 @@ impl Args {
         match self.state {
             State::None => {}
             State::Quoted => {
-                s = remove_quotes(s);
+                s = remove_quotes(s, &self.quotes);
             }
             State::Trimmed => {
                 s = trim(s);
             }
             State::QuotedTrimmed => {
-                s = remove_quotes(s);
+                s = remove_quotes(s, &self.quotes);
                 s = trim(s);
             }
             State::TrimmedQuoted => {
                 s = trim(s);
-                s = remove_quotes(s);
+                s = remove_quotes(s, &self.quotes);
             }
         }
 
@@ -670,7 +852,8 @@ impl Args {
     pub fn raw(&self) -> RawArguments<'_> {
         RawArguments {
             tokens: &self.args,
-            msg: &self.message,
+            msg: self.text(),
+            quotes: &self.quotes,
             quoted: false,
         }
     }
@@ -787,6 +970,17 @@ impl Args {
         &self.message
     }
 
+    /// Retrieves the prefix and the name the help command was invoked with.
+    ///
+    /// Returns `None` unless the framework created the arguments for its help
+    /// command.
+    #[inline]
+    pub fn invocation(&self) -> Option<(&str, &str)> {
+        self.invocation
+            .as_ref()
+            .map(|(prefix, name)| (prefix.as_str(), name.as_str()))
+    }
+
     /// Starting from the offset, return the remainder of available arguments.
     #[inline]
     pub fn rest(&self) -> &str {
@@ -804,7 +998,7 @@ impl Args {
 
         let (start, _) = self.span();
 
-        Some(&self.message[start..])
+        Some(&self.text()[start..])
     }
 
     /// Return the full amount of recognised arguments.
@@ -900,6 +1094,7 @@ impl<'a, T: FromStr> Iterator for Iter<'a, T> {
 pub struct RawArguments<'a> {
     msg: &'a str,
     tokens: &'a [Token],
+    quotes: &'a [char],
     quoted: bool,
 }
 
@@ -915,9 +1110,46 @@ impl<'a> Iterator for RawArguments<'a> {
         let mut s = &self.msg[start..end];
 
         if self.quoted {
-            s = remove_quotes(s);
+            s = remove_quotes(s, self.quotes);
         }
 
         Some(s)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{count_delimiters, Args, Delimiter};
+
+    #[test]
+    fn new_escaped() {
+        let mut args = Args::new_escaped(r"a\,b,c\\,d", &[Delimiter::Single(',')], '\\');
+
+        assert_eq!(args.len(), 3);
+        assert_eq!(args.single::<String>().unwrap(), "a,b");
+        assert_eq!(args.single::<String>().unwrap(), r"c\");
+        assert_eq!(args.single::<String>().unwrap(), "d");
+        assert_eq!(args.message(), r"a\,b,c\\,d");
+    }
+
+    #[test]
+    fn new_escaped_leaves_quoted_arguments_alone() {
+        let mut args = Args::new_escaped(r#""a\,b",c\,d"#, &[Delimiter::Single(',')], '\\');
+
+        assert_eq!(args.len(), 2);
+        assert_eq!(args.single_quoted::<String>().unwrap(), r"a\,b");
+        assert_eq!(args.single_quoted::<String>().unwrap(), "c,d");
+    }
+
+    #[test]
+    fn count_delimiters_between_arguments() {
+        let delimiters = [Delimiter::Single(','), Delimiter::Single('|')];
+
+        assert_eq!(count_delimiters("a,b|c", &delimiters, None, &['"']), 2);
+        assert_eq!(count_delimiters("a,,b", &delimiters, None, &['"']), 2);
+        assert_eq!(count_delimiters(r"a\,b,c", &delimiters, Some('\\'), &['"']), 1);
+        assert_eq!(count_delimiters(r#""a|b"|c"#, &delimiters, None, &['"']), 1);
+        assert_eq!(count_delimiters("`a|b`|c", &delimiters, None, &['`']), 1);
+        assert_eq!(count_delimiters("abc", &delimiters, None, &['"']), 0);
+    }
+}
@@ -56,7 +56,7 @@
 #[cfg(all(feature = "cache", feature = "http"))]
 use super::{
     Args, CommandGroup, CommandOptions, CheckResult,
-    has_correct_roles, HelpBehaviour, HelpOptions,
+    has_correct_roles, GroupOrder, HelpBehaviour, HelpOptions,
     has_correct_permissions, OnlyIn,
     structures::Command as InternalCommand,
 };
@@ -66,6 +66,7 @@ use crate::{
     client::Context,
     framework::standard::CommonOptions,
     model::channel::Message,
+    model::permissions::Permissions,
     Error,
     http::Http,
     model::id::{ChannelId, UserId},
@@ -91,7 +92,7 @@ macro_rules! format_command_name {
         match $behaviour {
             HelpBehaviour::Strike => format!("~~`{}`~~", $command_name),
             HelpBehaviour::Nothing => format!("`{}`", $command_name),
-            HelpBehaviour::Hide => continue,
+            HelpBehaviour::Hide | HelpBehaviour::Collapse => continue,
             HelpBehaviour::__Nonexhaustive => unreachable!(),
         }
     };
@@ -111,9 +112,15 @@ macro_rules! warn_about_failed_send {
 #[derive(Clone, Debug, Default)]
 pub struct GroupCommandsPair {
     pub name: &'static str,
+    pub summary: Option<&'static str>,
     pub prefixes: Vec<&'static str>,
     pub command_names: Vec<String>,
     pub sub_groups: Vec<GroupCommandsPair>,
+    /// Commands with a category, paired with their category's name.
+    /// They are listed under their category instead of this group.
+    pub categorised_command_names: Vec<(&'static str, String)>,
+    /// Amount of commands left out due to `HelpBehaviour::Collapse`.
+    pub collapsed_commands: usize,
 }
 
 /// A single suggested command containing its name and Levenshtein distance
@@ -135,8 +142,10 @@ pub struct Command<'a> {
     pub availability: &'a str,
     pub description: Option<&'static str>,
     pub usage: Option<&'static str>,
-    pub usage_sample: Vec<&'static str>,
+    pub usage_sample: Vec<(&'static str, &'static str)>,
     pub checks: Vec<String>,
+    pub required_permissions: Vec<&'static str>,
+    pub deprecation_notice: Option<&'static str>,
     pub(crate) _nonexhaustive: (),
 }
 
@@ -346,11 +355,14 @@ fn find_any_command_matches(
 }
 
 
+/// Decides how the help displays a group or command, given the restriction
+/// `only_in` resolved for it the same way as at dispatch.
 #[cfg(all(feature = "cache", feature = "http"))]
 async fn check_common_behaviour(
     cache: impl AsRef<Cache>,
     msg: &Message,
     options: &impl CommonOptions,
+    only_in: OnlyIn,
     owners: &HashSet<UserId>,
     help_options: &HelpOptions,
 ) -> HelpBehaviour {
@@ -358,8 +370,8 @@ async fn check_common_behaviour(
         return HelpBehaviour::Hide;
     }
 
-    if options.only_in() == OnlyIn::Dm && !msg.is_private() ||
-       options.only_in() == OnlyIn::Guild && msg.is_private() {
+    if only_in == OnlyIn::Dm && !msg.is_private() ||
+       only_in == OnlyIn::Guild && msg.is_private() {
         return help_options.wrong_channel;
     }
 
@@ -391,14 +403,16 @@ async fn check_command_behaviour(
     ctx: &Context,
     msg: &Message,
     options: &CommandOptions,
+    only_in: OnlyIn,
     owners: &HashSet<UserId>,
     help_options: &HelpOptions,
 ) -> HelpBehaviour {
-    let b = check_common_behaviour(&ctx, msg, &options, owners, help_options).await;
+    let only_in = options.only_in.resolve(only_in);
+    let b = check_common_behaviour(&ctx, msg, &options, only_in, owners, help_options).await;
 
     if b == HelpBehaviour::Nothing {
        if !options.owner_privilege || !owners.contains(&msg.author.id) {
-           for check in options.checks {
+           for check in options.priority_checks.iter().chain(options.checks) {
                if !check.check_in_help {
                    continue;
                }
@@ -415,6 +429,44 @@ async fn check_command_behaviour(
     b
 }
 
+/// Returns the command's usage for `locale`, falling back to its `usage`.
+#[cfg(all(feature = "cache", feature = "http"))]
+fn localized_usage(options: &CommandOptions, locale: &str) -> Option<&'static str> {
+    options
+        .localized_usages
+        .iter()
+        .find(|(l, _)| !locale.is_empty() && *l == locale)
+        .map(|(_, usage)| *usage)
+        .or(options.usage)
+}
+
+/// Returns the command's examples relevant to the author of `msg`, leaving out those
+/// requiring permissions the author lacks in the channel.
+#[cfg(all(feature = "cache", feature = "http"))]
+async fn relevant_examples(
+    cache: impl AsRef<Cache>,
+    options: &CommandOptions,
+    msg: &Message,
+) -> Vec<(&'static str, &'static str)> {
+    let requirements = options.example_permissions;
+
+    let permissions = if requirements.iter().all(|requires| requires.is_empty()) {
+        Permissions::empty()
+    } else if let Some(guild) = msg.guild(&cache).await {
+        guild.user_permissions_in(msg.channel_id, msg.author.id)
+    } else {
+        Permissions::empty()
+    };
+
+    options
+        .examples
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| requirements.get(*i).map_or(true, |requires| permissions.contains(*requires)))
+        .map(|(_, example)| *example)
+        .collect()
+}
+
 #[cfg(all(feature = "cache", feature = "http"))]
 #[allow(clippy::too_many_arguments)]
 async fn _nested_group_command_search<'rec, 'a: 'rec>(
@@ -425,15 +477,18 @@ async fn _nested_group_command_search<'rec, 'a: 'rec>(
     help_options: &'a HelpOptions,
     similar_commands: &'rec mut Vec<SuggestedCommandName>,
     owners: &'rec HashSet<UserId>,
+    only_in: OnlyIn,
 ) -> Result<CustomisedHelpData<'a>, ()> {
     for group in groups {
         let group = *group;
         let mut found: Option<&'static InternalCommand> = None;
+        let only_in = group.options.only_in.resolve(only_in);
 
         let group_behaviour = check_common_behaviour(
                 &ctx,
                 msg,
                 &group.options,
+                only_in.unless_overridden(group.options.commands, group.options.sub_groups),
                 &owners,
                 &help_options,
         ).await;
@@ -474,6 +529,7 @@ async fn _nested_group_command_search<'rec, 'a: 'rec>(
                     ctx,
                     msg,
                     &command.options,
+                    only_in,
                     &owners,
                     &help_options,
                 ).await {
@@ -496,6 +552,7 @@ async fn _nested_group_command_search<'rec, 'a: 'rec>(
                         ctx,
                         msg,
                         &command.options,
+                        only_in,
                         &owners,
                         &help_options,
                     ).await
@@ -517,9 +574,11 @@ async fn _nested_group_command_search<'rec, 'a: 'rec>(
                 });
             }
 
-            let available_text = if options.only_in == OnlyIn::Dm {
+            let only_in = options.only_in.resolve(only_in);
+
+            let available_text = if only_in == OnlyIn::Dm {
                 &help_options.dm_only_text
-            } else if options.only_in == OnlyIn::Guild {
+            } else if only_in == OnlyIn::Guild {
                 &help_options.guild_only_text
             } else {
                 &help_options.dm_and_guild_text
@@ -528,11 +587,18 @@ async fn _nested_group_command_search<'rec, 'a: 'rec>(
             similar_commands
                 .sort_unstable_by(|a, b| a.levenshtein_distance.cmp(&b.levenshtein_distance));
 
+            let group_checks: &[&Check] = if command.options.inherit_group_checks {
+                group.options.checks
+            } else {
+                &[]
+            };
+
             let check_names: Vec<String> = command
                 .options
-                .checks
+                .priority_checks
                 .iter()
-                .chain(group.options.checks.iter())
+                .chain(command.options.checks.iter())
+                .chain(group_checks.iter())
                 .filter_map(|check| {
                     if check.display_in_help {
                         Some(check.name.to_string())
@@ -560,11 +626,13 @@ async fn _nested_group_command_search<'rec, 'a: 'rec>(
                     group_name: group.name,
                     group_prefixes: &group.options.prefixes,
                     checks: check_names,
+                    required_permissions: options.required_permissions.get_permission_names(),
                     aliases: options.names[1..].to_vec(),
                     availability: available_text,
-                    usage: options.usage,
-                    usage_sample: options.examples.to_vec(),
+                    usage: localized_usage(options, help_options.locale),
+                    usage_sample: relevant_examples(&ctx, options, msg).await,
                     sub_commands: sub_command_names,
+                    deprecation_notice: options.deprecation_notice,
                     _nonexhaustive: (),
                 },
             });
@@ -578,6 +646,7 @@ async fn _nested_group_command_search<'rec, 'a: 'rec>(
             help_options,
             similar_commands,
             owners,
+            only_in,
         ).await {
             Ok(found) => return Ok(found),
             Err(()) => (),
@@ -589,6 +658,7 @@ async fn _nested_group_command_search<'rec, 'a: 'rec>(
 }
 
 #[cfg(all(feature = "cache", feature = "http"))]
+#[allow(clippy::too_many_arguments)]
 fn nested_group_command_search<'rec, 'a: 'rec>(
     ctx: &'rec Context,
     msg: &'rec Message,
@@ -597,6 +667,7 @@ fn nested_group_command_search<'rec, 'a: 'rec>(
     help_options: &'a HelpOptions,
     similar_commands: &'rec mut Vec<SuggestedCommandName>,
     owners: &'rec HashSet<UserId>,
+    only_in: OnlyIn,
 ) -> BoxFuture<'rec, Result<CustomisedHelpData<'a>, ()>> {
     _nested_group_command_search(
         ctx,
@@ -605,7 +676,8 @@ fn nested_group_command_search<'rec, 'a: 'rec>(
         name,
         help_options,
         similar_commands,
-        owners
+        owners,
+        only_in,
     ).boxed()
 }
 
@@ -631,6 +703,7 @@ async fn fetch_single_command<'a>(
         &help_options,
         &mut similar_commands,
         &owners,
+        OnlyIn::None,
     ).await {
         Ok(found) => Ok(found),
         Err(()) => Err(similar_commands),
@@ -646,6 +719,7 @@ async fn fill_eligible_commands<'a>(
     owners: &HashSet<UserId>,
     help_options: &'a HelpOptions,
     group: &'a CommandGroup,
+    only_in: OnlyIn,
     to_fill: &mut GroupCommandsPair,
     highest_formatter: &mut HelpBehaviour,
 ) {
@@ -662,6 +736,7 @@ async fn fill_eligible_commands<'a>(
                     &ctx,
                     msg,
                     &group.options,
+                    only_in.unless_overridden(group.options.commands, group.options.sub_groups),
                     owners,
                     help_options,
                 ).await
@@ -676,26 +751,50 @@ async fn fill_eligible_commands<'a>(
         let options = &command.options;
         let name = &options.names[0];
 
-        match &group_behaviour {
-            HelpBehaviour::Nothing => (),
-            _ => {
-                let name = format_command_name!(&group_behaviour, &name);
-                to_fill.command_names.push(name);
+        let behaviour = match group_behaviour {
+            HelpBehaviour::Nothing => check_command_behaviour(
+                ctx,
+                msg,
+                &command.options,
+                only_in,
+                owners,
+                help_options,
+            ).await,
+            behaviour => behaviour,
+        };
 
-                continue;
-            }
+        if behaviour == HelpBehaviour::Collapse {
+            to_fill.collapsed_commands += 1;
+
+            continue;
         }
 
-        let command_behaviour = check_command_behaviour(
-            ctx,
-            msg,
-            &command.options,
-            owners,
-            help_options,
-        ).await;
+        let name = format_command_name!(behaviour, &name);
+
+        match options.category {
+            Some(category) => to_fill.categorised_command_names.push((category, name)),
+            None => to_fill.command_names.push(name),
+        }
+    }
+}
+
+/// Moves the categorised commands of `group` and its sub-groups into
+/// `categories`, creating a listing for each new category.
+#[cfg(feature = "cache")]
+fn take_categorised_commands(group: &mut GroupCommandsPair, categories: &mut Vec<GroupCommandsPair>) {
+    for (category, name) in group.categorised_command_names.drain(..) {
+        match categories.iter_mut().find(|c| c.name == category) {
+            Some(listing) => listing.command_names.push(name),
+            None => categories.push(GroupCommandsPair {
+                name: category,
+                command_names: vec![name],
+                ..GroupCommandsPair::default()
+            }),
+        }
+    }
 
-        let name = format_command_name!(command_behaviour, &name);
-        to_fill.command_names.push(name);
+    for sub_group in &mut group.sub_groups {
+        take_categorised_commands(sub_group, categories);
     }
 }
 
@@ -710,11 +809,13 @@ fn fetch_all_eligible_commands_in_group<'rec, 'a: 'rec>(
     owners: &'rec HashSet<UserId>,
     help_options: &'a HelpOptions,
     group: &'a CommandGroup,
+    only_in: OnlyIn,
     highest_formatter: HelpBehaviour,
 ) -> BoxFuture<'rec, GroupCommandsPair> {
     async move {
         let mut group_with_cmds = GroupCommandsPair::default();
         let mut highest_formatter = highest_formatter;
+        let only_in = group.options.only_in.resolve(only_in);
 
         fill_eligible_commands(
             ctx,
@@ -723,6 +824,7 @@ fn fetch_all_eligible_commands_in_group<'rec, 'a: 'rec>(
             &owners,
             &help_options,
             &group,
+            only_in,
             &mut group_with_cmds,
             &mut highest_formatter,
         ).await;
@@ -741,6 +843,7 @@ fn fetch_all_eligible_commands_in_group<'rec, 'a: 'rec>(
                 &owners,
                 &help_options,
                 &sub_group,
+                only_in,
                 highest_formatter,
             ).await;
 
@@ -762,17 +865,32 @@ async fn create_command_group_commands_pair_from_groups<'a>(
     help_options: &'a HelpOptions,
 ) -> Vec<GroupCommandsPair> {
     let mut listed_groups: Vec<GroupCommandsPair> = Vec::default();
+    let mut categories: Vec<GroupCommandsPair> = Vec::default();
+
+    let mut groups = groups.to_vec();
+
+    match help_options.sort_groups_by {
+        GroupOrder::Weight => groups.sort_by_key(|group| group.options.order),
+        GroupOrder::Name => groups.sort_by_key(|group| group.name),
+        GroupOrder::Declaration | GroupOrder::__Nonexhaustive => {},
+    }
 
     for group in groups {
-        let group = *group;
+        let mut group_with_cmds =
+            create_single_group(ctx, msg, group, OnlyIn::None, &owners, &help_options).await;
 
-        let group_with_cmds = create_single_group(ctx, msg, group, &owners, &help_options).await;
+        take_categorised_commands(&mut group_with_cmds, &mut categories);
 
-        if !group_with_cmds.command_names.is_empty() || !group_with_cmds.sub_groups.is_empty() {
+        if !group_with_cmds.command_names.is_empty()
+            || !group_with_cmds.sub_groups.is_empty()
+            || group_with_cmds.collapsed_commands > 0
+        {
             listed_groups.push(group_with_cmds);
         }
     }
 
+    listed_groups.extend(categories);
+
     listed_groups
 }
 
@@ -782,6 +900,7 @@ async fn create_single_group(
     ctx: &Context,
     msg: &Message,
     group: &CommandGroup,
+    only_in: OnlyIn,
     owners: &HashSet<UserId>,
     help_options: &HelpOptions,
 ) -> GroupCommandsPair {
@@ -792,10 +911,12 @@ async fn create_single_group(
         &owners,
         &help_options,
         &group,
+        only_in,
         HelpBehaviour::Nothing,
     ).await;
 
     group_with_cmds.name = group.name;
+    group_with_cmds.summary = group.options.summary;
 
     group_with_cmds
 }
@@ -827,6 +948,29 @@ pub fn searched_lowercase<'rec, 'a: 'rec>(
     owners: &'rec HashSet<UserId>,
     help_options: &'a HelpOptions,
     searched_named_lowercase: &'rec mut String,
+) -> BoxFuture<'rec, Option<CustomisedHelpData<'a>>> {
+    searched_lowercase_in(
+        ctx,
+        msg,
+        group,
+        OnlyIn::None,
+        owners,
+        help_options,
+        searched_named_lowercase,
+    )
+}
+
+/// Searches `group` like `searched_lowercase`, with `only_in` being the
+/// restriction it inherits from its enclosing groups.
+#[cfg(feature = "cache")]
+fn searched_lowercase_in<'rec, 'a: 'rec>(
+    ctx: &'rec Context,
+    msg: &'rec Message,
+    group: &'rec CommandGroup,
+    only_in: OnlyIn,
+    owners: &'rec HashSet<UserId>,
+    help_options: &'a HelpOptions,
+    searched_named_lowercase: &'rec mut String,
 ) -> BoxFuture<'rec, Option<CustomisedHelpData<'a>>> {
     async move {
         let is_prefixless_group = {
@@ -851,10 +995,16 @@ pub fn searched_lowercase<'rec, 'a: 'rec>(
             });
 
         if is_prefixless_group || is_word_prefix {
-            let single_group =
-                create_single_group(ctx, msg, &group, owners, &help_options).await;
+            let mut single_group =
+                create_single_group(ctx, msg, &group, only_in, owners, &help_options).await;
+            let mut categories = Vec::new();
+
+            take_categorised_commands(&mut single_group, &mut categories);
+
+            if !single_group.command_names.is_empty() || !categories.is_empty() {
+                let mut groups = vec![single_group];
+                groups.extend(categories);
 
-            if !single_group.command_names.is_empty() {
                 return Some(CustomisedHelpData::GroupedCommands {
                     help_description: group
                         .options
@@ -862,15 +1012,18 @@ pub fn searched_lowercase<'rec, 'a: 'rec>(
                         .as_ref()
                         .map(|s| s.to_string())
                         .unwrap_or_default(),
-                    groups: vec![single_group],
+                    groups,
                 });
             }
         } else if progressed || group.options.prefixes.is_empty() {
+            let only_in = group.options.only_in.resolve(only_in);
+
             for sub_group in group.options.sub_groups {
-                if let Some(found_set) = searched_lowercase(
+                if let Some(found_set) = searched_lowercase_in(
                     ctx,
                     msg,
                     sub_group,
+                    only_in,
                     owners,
                     help_options,
                     searched_named_lowercase,
@@ -884,6 +1037,18 @@ pub fn searched_lowercase<'rec, 'a: 'rec>(
     }.boxed()
 }
 
+/// Replaces the `{prefix}` and `{command}` tokens in `text` with the prefix
+/// and the name the help command was invoked with. If they are unknown, the
+/// prefix is left empty and the first of the help command's `names` is used.
+#[cfg(feature = "cache")]
+fn replace_help_text_tokens(args: &Args, names: &[&str], text: &str) -> String {
+    let (prefix, command) = args
+        .invocation()
+        .unwrap_or(("", names.get(0).copied().unwrap_or_default()));
+
+    text.replace("{prefix}", prefix).replace("{command}", command)
+}
+
 /// Iterates over all commands and forges them into a `CustomisedHelpData`,
 /// taking `HelpOptions` into consideration when deciding on whether a command
 /// shall be picked and in what textual format.
@@ -938,13 +1103,15 @@ pub async fn create_customised_help_data<'a>(
         &help_options.strikethrough_commands_tip_in_guild
     };
 
+    let individual_command_tip = replace_help_text_tokens(args, help_options.names, help_options.individual_command_tip);
+
     let description = if let Some(ref strikethrough_command_text) = strikethrough_command_tip {
         format!(
             "{}\n{}",
-            &help_options.individual_command_tip, &strikethrough_command_text
+            &individual_command_tip, &strikethrough_command_text
         )
     } else {
-        help_options.individual_command_tip.to_string()
+        individual_command_tip
     };
 
     let listed_groups = create_command_group_commands_pair_from_groups(
@@ -967,6 +1134,14 @@ pub async fn create_customised_help_data<'a>(
     }
 }
 
+/// Describes how many commands of a group were collapsed.
+#[cfg(all(feature = "cache", feature = "http"))]
+fn collapsed_text(count: usize, (singular, plural): (&str, &str)) -> String {
+    let text = if count == 1 { singular } else { plural };
+
+    text.replace("{count}", &count.to_string())
+}
+
 /// Flattens a group with all its nested sub-groups into the passed `group_text`
 /// buffer.
 /// If `nest_level` is `0`, this function will skip the group's name.
@@ -987,6 +1162,10 @@ fn flatten_group_to_string(
         );
     }
 
+    if let Some(summary) = group.summary {
+        let _ = writeln!(group_text, "{}*{}*", &repeated_indent_str, summary);
+    }
+
     if !group.prefixes.is_empty() {
         let _ = writeln!(group_text,
             "{}{}: `{}`",
@@ -1007,9 +1186,18 @@ fn flatten_group_to_string(
 
     let _ = writeln!(group_text, "{}", joined_commands);
 
+    if group.collapsed_commands > 0 {
+        let collapsed = collapsed_text(group.collapsed_commands, help_options.collapsed_commands_text);
+
+        let _ = writeln!(group_text, "{}{}", &repeated_indent_str, collapsed);
+    }
+
     for sub_group in &group.sub_groups {
 
-        if !(sub_group.command_names.is_empty() && sub_group.sub_groups.is_empty()) {
+        if !(sub_group.command_names.is_empty()
+            && sub_group.sub_groups.is_empty()
+            && sub_group.collapsed_commands == 0)
+        {
             let mut sub_group_text = String::default();
 
             flatten_group_to_string(
@@ -1044,6 +1232,10 @@ fn flatten_group_to_plain_string(
         );
     }
 
+    if let Some(summary) = group.summary {
+        let _ = write!(group_text, " - *{}*", summary);
+    }
+
     if group.prefixes.is_empty() {
         let _ = write!(group_text, ": ");
     } else {
@@ -1058,6 +1250,14 @@ fn flatten_group_to_plain_string(
 
     let _ = write!(group_text, "{}", joined_commands);
 
+    if group.collapsed_commands > 0 {
+        let separator = if group.command_names.is_empty() { "" } else { ", " };
+
+        let collapsed = collapsed_text(group.collapsed_commands, help_options.collapsed_commands_text);
+
+        let _ = write!(group_text, "{}{}", separator, collapsed);
+    }
+
     for sub_group in &group.sub_groups {
         let mut sub_group_text = String::default();
 
@@ -1082,29 +1282,98 @@ async fn send_grouped_commands_embed(
     help_description: &str,
     groups: &[GroupCommandsPair],
     colour: Colour,
+    footer: &str,
 ) -> Result<Message, Error> {
-    channel_id.send_message(&http, |m| {
+    let fields = groups.iter().map(|group| {
+        let mut embed_text = String::default();
+
+        flatten_group_to_string(
+            &mut embed_text,
+            &group,
+            0,
+            &help_options,
+        );
+
+        truncate_field(&mut embed_text, help_options.max_field_length);
+
+        (group.name, embed_text)
+    }).collect::<Vec<_>>();
+
+    let mut pages = fields.chunks(std::cmp::max(help_options.max_embed_fields, 1)).peekable();
+    let first_page = pages.next().unwrap_or(&[]);
+    let is_last = pages.peek().is_none();
+
+    let response = channel_id.send_message(&http, |m| {
         m.embed(|embed| {
             embed.colour(colour);
             embed.description(help_description);
+            embed.fields(first_page.iter().map(|(name, text)| (name, text, true)));
 
-            for group in groups {
-                let mut embed_text = String::default();
-
-                flatten_group_to_string(
-                    &mut embed_text,
-                    &group,
-                    0,
-                    &help_options,
-                );
-
-                embed.field(group.name, &embed_text, true);
+            if !footer.is_empty() && is_last {
+                embed.footer(|f| f.text(footer));
             }
 
             embed
         });
         m
-    }).await
+    }).await?;
+
+    while let Some(page) = pages.next() {
+        let is_last = pages.peek().is_none();
+
+        channel_id.send_message(&http, |m| {
+            m.embed(|embed| {
+                embed.colour(colour);
+                embed.fields(page.iter().map(|(name, text)| (name, text, true)));
+
+                if !footer.is_empty() && is_last {
+                    embed.footer(|f| f.text(footer));
+                }
+
+                embed
+            });
+            m
+        }).await?;
+    }
+
+    Ok(response)
+}
+
+/// Cuts `text` down to at most `max_length` bytes, on a character boundary.
+#[cfg(all(feature = "cache", feature = "http"))]
+fn truncate_field(text: &mut String, max_length: usize) {
+    if text.len() <= max_length {
+        return;
+    }
+
+    let mut end = max_length;
+
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+
+    text.truncate(end);
+}
+
+/// Formats an invocation of `command` with `args`, as shown in usage and example lines.
+#[cfg(all(feature = "cache", feature = "http"))]
+fn format_invocation(prefix: &str, command: &Command<'_>, args: &str) -> String {
+    match command.group_prefixes.get(0) {
+        Some(first_prefix) => format!("`{}{} {} {}`", prefix, first_prefix, command.name, args),
+        None => format!("`{}{} {}`", prefix, command.name, args),
+    }
+}
+
+/// Formats an example invocation of `command`, followed by its expected output if it has one.
+#[cfg(all(feature = "cache", feature = "http"))]
+fn format_example(prefix: &str, command: &Command<'_>, (input, output): (&str, &str)) -> String {
+    let mut text = format_invocation(prefix, command, input);
+
+    if !output.is_empty() {
+        let _ = write!(text, " → `{}`", output);
+    }
+
+    text
 }
 
 /// Sends embed showcasing information about a single command.
@@ -1115,6 +1384,8 @@ async fn send_single_command_embed(
     channel_id: ChannelId,
     command: &Command<'_>,
     colour: Colour,
+    footer: &str,
+    prefix: &str,
 ) -> Result<Message, Error> {
     channel_id.send_message(&http, |m| {
         m.embed(|embed| {
@@ -1125,35 +1396,22 @@ async fn send_single_command_embed(
                 embed.description(desc);
             }
 
+            if let Some(notice) = command.deprecation_notice {
+                embed.field(&help_options.deprecated_label, notice, false);
+            }
+
             if let Some(ref usage) = command.usage {
-                let full_usage_text = if let Some(first_prefix) = command.group_prefixes.get(0) {
-                    format!("`{} {} {}`", first_prefix, command.name, usage)
-                } else {
-                    format!("`{} {}`", command.name, usage)
-                };
+                let full_usage_text = format_invocation(prefix, command, usage);
 
                 embed.field(&help_options.usage_label, full_usage_text, true);
             }
 
             if !command.usage_sample.is_empty() {
-                let full_example_text =
-                    if let Some(first_prefix) = command.group_prefixes.get(0) {
-                        let format_example = |example| {
-                            format!("`{} {} {}`\n", first_prefix, command.name, example)
-                        };
-                        command
-                           .usage_sample
-                           .iter()
-                           .map(format_example)
-                           .collect::<String>()
-                    } else {
-                        let format_example = |example| format!("`{} {}`\n", command.name, example);
-                        command
-                           .usage_sample
-                           .iter()
-                           .map(format_example)
-                           .collect::<String>()
-                    };
+                let full_example_text = command
+                    .usage_sample
+                    .iter()
+                    .map(|example| format!("{}\n", format_example(prefix, command, *example)))
+                    .collect::<String>();
                 embed.field(&help_options.usage_sample_label, full_example_text, true);
             }
 
@@ -1167,7 +1425,9 @@ async fn send_single_command_embed(
                 );
             }
 
-            embed.field(&help_options.available_text, &command.availability, true);
+            if help_options.show_availability {
+                embed.field(&help_options.available_text, &command.availability, true);
+            }
 
             if !command.checks.is_empty() {
                 embed.field(
@@ -1177,6 +1437,14 @@ async fn send_single_command_embed(
                 );
             }
 
+            if help_options.show_permissions && !command.required_permissions.is_empty() {
+                embed.field(
+                    &help_options.permissions_label,
+                    command.required_permissions.join(", "),
+                    true,
+                );
+            }
+
             if !command.sub_commands.is_empty() {
                 embed.field(
                     &help_options.sub_commands_label,
@@ -1185,6 +1453,10 @@ async fn send_single_command_embed(
                 );
             }
 
+            if !footer.is_empty() {
+                embed.footer(|f| f.text(footer));
+            }
+
             embed
         });
         m
@@ -1274,6 +1546,8 @@ pub async fn with_embeds(
 ) -> Option<Message> {
     let formatted_help =
         create_customised_help_data(ctx, msg, &args, &groups, &owners, help_options).await;
+    let footer = replace_help_text_tokens(&args, help_options.names, help_options.footer_text);
+    let prefix = replace_help_text_tokens(&args, help_options.names, help_options.prefix_display);
 
     let response_result = match formatted_help {
         CustomisedHelpData::SuggestedCommands {
@@ -1304,6 +1578,7 @@ pub async fn with_embeds(
             &help_description,
             &groups,
             help_options.embed_success_colour,
+            &footer,
         ).await,
         CustomisedHelpData::SingleCommand { ref command } => send_single_command_embed(
             &ctx.http,
@@ -1311,6 +1586,8 @@ pub async fn with_embeds(
             msg.channel_id,
             &command,
             help_options.embed_success_colour,
+            &footer,
+            &prefix,
         ).await,
         CustomisedHelpData::__Nonexhaustive => unreachable!(),
     };
@@ -1350,7 +1627,7 @@ fn grouped_commands_to_plain_string(
 
 /// Turns a single command into a `String` taking plain help format into account.
 #[cfg(all(feature = "cache", feature = "http"))]
-fn single_command_to_plain_string(help_options: &HelpOptions, command: &Command<'_>) -> String {
+fn single_command_to_plain_string(help_options: &HelpOptions, command: &Command<'_>, prefix: &str) -> String {
     let mut result = String::default();
     let _ = writeln!(result, "__**{}**__", command.name);
 
@@ -1371,48 +1648,30 @@ fn single_command_to_plain_string(help_options: &HelpOptions, command: &Command<
         );
     };
 
+    if let Some(notice) = command.deprecation_notice {
+        let _ = writeln!(
+            result,
+            "**{}**: {}",
+            help_options.deprecated_label, notice
+        );
+    };
+
     if let Some(ref usage) = command.usage {
-        if let Some(first_prefix) = command.group_prefixes.get(0) {
-            let _ = writeln!(
-                result,
-                "**{}**: `{} {} {}`",
-                help_options.usage_label, first_prefix, command.name, usage
-            );
-        } else {
-            let _ = writeln!(
-                result,
-                "**{}**: `{} {}`",
-                help_options.usage_label, command.name, usage
-            );
-        }
+        let _ = writeln!(
+            result,
+            "**{}**: {}",
+            help_options.usage_label,
+            format_invocation(prefix, command, usage)
+        );
     }
 
-    if !command.usage_sample.is_empty() {
-        if let Some(first_prefix) = command.group_prefixes.get(0) {
-            let format_example = |example| {
-                let _ = writeln!(
-                    result,
-                    "**{}**: `{} {} {}`",
-                    help_options.usage_sample_label, first_prefix, command.name, example
-                );
-            };
-            command
-                .usage_sample
-                .iter()
-                .for_each(format_example);
-        } else {
-            let format_example = |example| {
-                let _ = writeln!(
-                    result,
-                    "**{}**: `{} {}`",
-                    help_options.usage_sample_label, command.name, example
-                );
-            };
-            command
-                .usage_sample
-                .iter()
-                .for_each(format_example);
-        }
+    for example in &command.usage_sample {
+        let _ = writeln!(
+            result,
+            "**{}**: {}",
+            help_options.usage_sample_label,
+            format_example(prefix, command, *example)
+        );
     }
 
     let _ = writeln!(
@@ -1420,11 +1679,22 @@ fn single_command_to_plain_string(help_options: &HelpOptions, command: &Command<
         "**{}**: {}",
         help_options.grouped_label, command.group_name
     );
-    let _ = writeln!(
-        result,
-        "**{}**: {}",
-        help_options.available_text, command.availability
-    );
+    if help_options.show_availability {
+        let _ = writeln!(
+            result,
+            "**{}**: {}",
+            help_options.available_text, command.availability
+        );
+    }
+
+    if help_options.show_permissions && !command.required_permissions.is_empty() {
+        let _ = writeln!(
+            result,
+            "**{}**: {}",
+            help_options.permissions_label,
+            command.required_permissions.join(", ")
+        );
+    }
 
     result
 }
@@ -1471,8 +1741,10 @@ pub async fn plain(
 ) -> Option<Message> {
     let formatted_help =
         create_customised_help_data(ctx, msg, &args, &groups, &owners, help_options).await;
+    let footer = replace_help_text_tokens(&args, help_options.names, help_options.footer_text);
+    let prefix = replace_help_text_tokens(&args, help_options.names, help_options.prefix_display);
 
-    let result = match formatted_help {
+    let mut result = match formatted_help {
         CustomisedHelpData::SuggestedCommands {
             ref help_description,
             ref suggestions,
@@ -1485,17 +1757,147 @@ pub async fn plain(
             ref groups,
         } => grouped_commands_to_plain_string(&help_options, &help_description, &groups),
         CustomisedHelpData::SingleCommand { ref command } => {
-            single_command_to_plain_string(&help_options, &command)
+            single_command_to_plain_string(&help_options, &command, &prefix)
         },
         CustomisedHelpData::__Nonexhaustive => unreachable!(),
     };
 
-    match msg.channel_id.say(&ctx, result).await {
-        Ok(response) => Some(response),
+    let listed = match formatted_help {
+        CustomisedHelpData::GroupedCommands { .. } | CustomisedHelpData::SingleCommand { .. } => true,
+        _ => false,
+    };
+
+    if listed && !footer.is_empty() {
+        let _ = write!(result, "\n{}", footer);
+    }
+
+    let mut pages = split_pages(&result, help_options.paginate_after).into_iter();
+    let first_page = pages.next().unwrap_or_default();
+
+    let response = match msg.channel_id.say(&ctx, first_page).await {
+        Ok(response) => response,
         Err(why) => {
             warn_about_failed_send!(&formatted_help, why);
-            None
+            return None;
+        }
+    };
+
+    for page in pages {
+        if let Err(why) = msg.channel_id.say(&ctx, page).await {
+            warn_about_failed_send!(&formatted_help, why);
+            break;
+        }
+    }
+
+    Some(response)
+}
+
+/// Splits `text` into pages of at most `max_length` bytes, preferably right
+/// after a line break. If `max_length` is `0`, `text` is kept whole.
+#[cfg(all(feature = "cache", feature = "http"))]
+fn split_pages(text: &str, max_length: usize) -> Vec<&str> {
+    if max_length == 0 {
+        return vec![text];
+    }
+
+    let mut pages = Vec::new();
+    let mut rest = text;
+
+    while rest.len() > max_length {
+        let mut end = max_length;
+
+        while !rest.is_char_boundary(end) {
+            end -= 1;
+        }
+
+        if let Some(newline) = rest[..end].rfind('\n') {
+            end = newline + 1;
+        } else if end == 0 {
+            end = rest.chars().next().map_or(rest.len(), char::len_utf8);
         }
+
+        let (page, remainder) = rest.split_at(end);
+        pages.push(page);
+        rest = remainder;
+    }
+
+    if !rest.is_empty() || pages.is_empty() {
+        pages.push(rest);
+    }
+
+    pages
+}
+
+#[cfg(test)]
+#[cfg(all(feature = "cache", feature = "http"))]
+mod help_text_token_tests {
+    use super::{replace_help_text_tokens, Args};
+    use crate::framework::standard::Delimiter;
+
+    #[test]
+    fn invoked_prefix_and_name() {
+        let args = Args::new("help", &[Delimiter::Single(' ')]).with_invocation("!", "h");
+        let text = replace_help_text_tokens(&args, &["help", "h"], "Use `{prefix}{command} <command>`");
+
+        assert_eq!(text, "Use `!h <command>`");
+    }
+
+    #[test]
+    fn unknown_invocation() {
+        let args = Args::new("", &[Delimiter::Single(' ')]);
+        let text = replace_help_text_tokens(&args, &["h", "help"], "Use `{prefix}{command} <command>`");
+
+        assert_eq!(text, "Use `h <command>`");
+    }
+}
+
+#[cfg(test)]
+#[cfg(all(feature = "cache", feature = "http"))]
+mod collapsed_text_tests {
+    use super::collapsed_text;
+
+    const TEXTS: (&str, &str) = ("*{count} hidden command*", "*{count} hidden commands*");
+
+    #[test]
+    fn singular_and_plural() {
+        assert_eq!(collapsed_text(1, TEXTS), "*1 hidden command*");
+        assert_eq!(collapsed_text(3, TEXTS), "*3 hidden commands*");
+    }
+
+    #[test]
+    fn without_count() {
+        assert_eq!(collapsed_text(2, ("*Some are hidden*", "*Some are hidden*")), "*Some are hidden*");
+    }
+}
+
+#[cfg(test)]
+#[cfg(all(feature = "cache", feature = "http"))]
+mod localized_usage_tests {
+    use super::{localized_usage, CommandOptions};
+
+    #[test]
+    fn matching_locale() {
+        let options = CommandOptions {
+            usage: Some("<user>"),
+            localized_usages: &[("de", "<nutzer>"), ("fr", "<utilisateur>")],
+            ..CommandOptions::default()
+        };
+
+        assert_eq!(localized_usage(&options, "fr"), Some("<utilisateur>"));
+        assert_eq!(localized_usage(&options, "de"), Some("<nutzer>"));
+    }
+
+    #[test]
+    fn falls_back_to_usage() {
+        let options = CommandOptions {
+            usage: Some("<user>"),
+            localized_usages: &[("fr", "<utilisateur>")],
+            ..CommandOptions::default()
+        };
+
+        assert_eq!(localized_usage(&options, "es"), Some("<user>"));
+        assert_eq!(localized_usage(&options, ""), Some("<user>"));
+        assert_eq!(localized_usage(&CommandOptions::default(), "fr"), None);
     }
 }
 
@@ -1580,3 +1982,48 @@ mod matrix_tests {
         matrix[(0, 0)];
     }
 }
+
+#[cfg(test)]
+#[cfg(all(feature = "cache", feature = "http"))]
+mod truncate_tests {
+    use super::truncate_field;
+
+    #[test]
+    fn short_text_is_kept() {
+        let mut text = String::from("ping");
+        truncate_field(&mut text, 1024);
+        assert_eq!(text, "ping");
+    }
+
+    #[test]
+    fn cut_on_char_boundary() {
+        let mut text = String::from("pïng");
+        truncate_field(&mut text, 2);
+        assert_eq!(text, "p");
+
+        let mut text = String::from("pïng");
+        truncate_field(&mut text, 3);
+        assert_eq!(text, "pï");
+    }
+}
+
+#[cfg(test)]
+#[cfg(all(feature = "cache", feature = "http"))]
+mod split_pages_tests {
+    use super::split_pages;
+
+    #[test]
+    fn disabled() {
+        assert_eq!(split_pages("a\nb\nc", 0), vec!["a\nb\nc"]);
+    }
+
+    #[test]
+    fn split_after_line_break() {
+        assert_eq!(split_pages("ab\ncd\nef", 6), vec!["ab\ncd\n", "ef"]);
+    }
+
+    #[test]
+    fn split_long_line() {
+        assert_eq!(split_pages("pïng", 2), vec!["p", "ï", "ng"]);
+    }
+}
@@ -1,6 +1,25 @@
 pub mod help_commands;
+/// Macros for declaring commands, groups, checks and hooks.
+///
+/// Options can be checked at compile time with `assert_command_options`; a mismatch
+/// fails the build:
+///
+/// ```rust,compile_fail
+/// use serenity::client::Context;
+/// use serenity::model::channel::Message;
+/// use serenity::framework::standard::CommandResult;
+/// use serenity::framework::standard::macros::{assert_command_options, command};
+///
+/// #[command(as_const)]
+/// #[bucket("mod")]
+/// async fn ban(_ctx: &Context, _msg: &Message) -> CommandResult {
+///     Ok(())
+/// }
+///
+/// assert_command_options!(BAN_COMMAND_OPTIONS, bucket = "admin");
+/// ```
 pub mod macros {
-    pub use command_attr::{command, group, help, check, hook};
+    pub use command_attr::{assert_command_options, command, command_preset, group, help, check, hook};
 }
 
 mod args;
@@ -17,6 +36,7 @@ pub use structures::buckets::BucketBuilder;
 
 use parse::{ParseError, Invoke};
 use parse::map::{CommandMap, GroupMap, Map};
+use args::count_delimiters;
 
 use super::Framework;
 use crate::client::Context;
@@ -33,6 +53,7 @@ use tokio::sync::Mutex;
 use futures::future::BoxFuture;
 use uwl::Stream;
 use async_trait::async_trait;
+use log::{debug, warn};
 
 #[cfg(feature = "cache")]
 use crate::model::channel::Channel;
@@ -66,6 +87,12 @@ pub enum DispatchError {
     /// When the requested command can only be ran in guilds, or the bot doesn't
     /// support DMs.
     OnlyForGuilds,
+    /// When the requested command is restricted to guilds other than the one
+    /// it was invoked in.
+    GuildNotAllowed,
+    /// When the requested command is restricted to users other than the
+    /// requester.
+    UserNotAllowed,
     /// When the requested command can only be used by bot owners.
     OnlyForOwners,
     /// When the requested command requires one role.
@@ -76,6 +103,14 @@ pub enum DispatchError {
     NotEnoughArguments { min: u16, given: usize },
     /// When there are too many arguments.
     TooManyArguments { max: u16, given: usize },
+    /// When the arguments do not contain the exact amount of delimiters.
+    WrongDelimiterCount { expected: u16, given: usize },
+    /// When the command requester has not been a member of the guild for long
+    /// enough. Both values are in seconds.
+    MembershipTooRecent { required: u64, elapsed: u64 },
+    /// When the command requires the requester to be in a voice channel, but
+    /// they are not.
+    NotInVoiceChannel,
     /// When the command was requested by a bot user when they are set to be
     /// ignored.
     IgnoredBot,
@@ -100,6 +135,8 @@ type PrefixOnlyHook = for<'fut> fn(&'fut Context, &'fut Message) -> BoxFuture<'f
 #[derive(Default)]
 pub struct StandardFramework {
     groups: Vec<(&'static CommandGroup, Map)>,
+    /// The prefixes declared by the commands of `groups`, longest first.
+    command_prefixes: Vec<&'static str>,
     buckets: Mutex<HashMap<String, Bucket>>,
     before: Option<BeforeHook>,
     after: Option<AfterHook>,
@@ -249,7 +286,14 @@ impl StandardFramework {
         command: &'static CommandOptions,
         group: &'static GroupOptions,
     ) -> Option<DispatchError> {
-        if let Some(min) = command.min_args {
+        // A command with sub commands reaching this point was invoked without one of them.
+        let base_min_args = if command.sub_commands.is_empty() {
+            None
+        } else {
+            command.base_min_args
+        };
+
+        if let Some(min) = base_min_args.or(command.min_args).or(group.default_min_args) {
             if args.len() < min as usize {
                 return Some(DispatchError::NotEnoughArguments {
                     min,
@@ -258,7 +302,7 @@ impl StandardFramework {
             }
         }
 
-        if let Some(max) = command.max_args {
+        if let Some(max) = command.max_args.or(group.default_max_args) {
             if args.len() > max as usize {
                 return Some(DispatchError::TooManyArguments {
                     max,
@@ -267,6 +311,29 @@ impl StandardFramework {
             }
         }
 
+        if let Some(expected) = command.num_delimiters {
+            let delimiters = command
+                .delimiters
+                .iter()
+                .map(|d| Delimiter::from(*d))
+                .collect::<Vec<_>>();
+
+            // Only the delimiters splitting arguments count, not quoted or escaped ones.
+            let given = count_delimiters(
+                args.message(),
+                &delimiters,
+                command.delimiter_escape,
+                command.quotes,
+            );
+
+            if given != expected as usize {
+                return Some(DispatchError::WrongDelimiterCount {
+                    expected,
+                    given,
+                });
+            }
+        }
+
         if (group.owner_privilege && command.owner_privilege)
             && self.config.owners.contains(&msg.author.id)
         {
@@ -277,6 +344,18 @@ impl StandardFramework {
             return Some(DispatchError::BlockedUser);
         }
 
+        if !command.allowed_guilds.is_empty() {
+            match msg.guild_id {
+                Some(guild_id) if command.allowed_guilds.contains(&guild_id.0) => {},
+                Some(_) => return Some(DispatchError::GuildNotAllowed),
+                None => return Some(DispatchError::OnlyForGuilds),
+            }
+        }
+
+        if !command.allowed_users.is_empty() && !command.allowed_users.contains(&msg.author.id.0) {
+            return Some(DispatchError::UserNotAllowed);
+        }
+
         #[cfg(feature = "cache")]
         {
             if let Some(Channel::Guild(channel)) = msg.channel_id.to_channel_cached(&ctx).await {
@@ -294,15 +373,37 @@ impl StandardFramework {
             }
         }
 
+        if let (Some(required), Some(guild_id)) = (command.min_membership_seconds, msg.guild_id) {
+            // Members missing from the cache are fetched; an unknown join date is rejected.
+            let joined_at = guild_id.member(ctx, msg.author.id).await.ok().and_then(|m| m.joined_at);
+
+            let elapsed = joined_at.map_or(0, |joined_at| {
+                let elapsed = msg.timestamp.signed_duration_since(joined_at).num_seconds();
+
+                std::cmp::max(elapsed, 0) as u64
+            });
+
+            if joined_at.is_none() || elapsed < required {
+                return Some(DispatchError::MembershipTooRecent { required, elapsed });
+            }
+        }
+
+        if command.requires_voice && !in_voice_channel(ctx, msg).await {
+            return Some(DispatchError::NotInVoiceChannel);
+        }
+
         if !self.config.allowed_channels.is_empty() &&
            !self.config.allowed_channels.contains(&msg.channel_id) {
             return Some(DispatchError::BlockedChannel);
         }
 
-        {
+        // A command's own bucket takes precedence over its group's.
+        let bucket = command.bucket.or(group.bucket);
+
+        if bucket.is_some() && !bypasses_cooldown(ctx, msg, command).await {
             let mut buckets = self.buckets.lock().await;
 
-            if let Some(ref mut bucket) = command.bucket.as_ref().and_then(|b| buckets.get_mut(*b)) {
+            if let Some(ref mut bucket) = bucket.and_then(|b| buckets.get_mut(b)) {
                 let rate_limit = bucket.take(msg.author.id.0);
 
                 let apply = match bucket.check.as_ref() {
@@ -318,7 +419,17 @@ impl StandardFramework {
             }
         }
 
-        for check in group.checks.iter().chain(command.checks.iter()) {
+        let group_checks: &[&Check] = if command.inherit_group_checks {
+            group.checks
+        } else {
+            &[]
+        };
+
+        let checks = command.priority_checks.iter()
+            .chain(group_checks.iter())
+            .chain(command.checks.iter());
+
+        for check in checks {
             let res = (check.function)(ctx, msg, args, command).await;
 
             if let CheckResult::Failure(r) = res {
@@ -401,6 +512,7 @@ impl StandardFramework {
         };
 
         self.groups.push((group, map));
+        parse::add_command_prefixes(&mut self.command_prefixes, group);
     }
 
     /// Removes a group from being used in the framework. Primary use-case is runtime modification
@@ -410,7 +522,13 @@ impl StandardFramework {
     /// it's not intended to be chained as the other commands are.
     pub fn group_remove(&mut self, group: &'static CommandGroup) {
         // Iterates through the vector and if a given group _doesn't_ match, we retain it
-        self.groups.retain(|&(g, _)| g != group)
+        self.groups.retain(|&(g, _)| g != group);
+
+        self.command_prefixes.clear();
+
+        for &(group, _) in &self.groups {
+            parse::add_command_prefixes(&mut self.command_prefixes, group);
+        }
     }
 
     /// Specify the function that's called in case a command wasn't executed for one reason or
@@ -619,10 +737,22 @@ impl Framework for StandardFramework {
 
         stream.take_while_char(|c| c.is_whitespace());
 
-        let prefix = parse::prefix(&mut ctx, &msg, &mut stream, &self.config).await;
+        let mut prefix = parse::prefix(&mut ctx, &msg, &mut stream, &self.config).await;
+        let mut command_prefix = None;
+
+        if prefix.is_none() {
+            command_prefix = parse::command_prefix(&mut stream, &self.command_prefixes);
+            prefix = command_prefix.map(std::borrow::Cow::Borrowed);
+        }
 
         if prefix.is_some() && stream.rest().is_empty() {
-            if let Some(prefix_only) = &self.prefix_only {
+            // A command's own prefix is not the framework's, so a message of
+            // nothing but one is a normal message.
+            if command_prefix.is_some() {
+                if let Some(normal) = &self.normal_message {
+                    normal(&mut ctx, &msg).await;
+                }
+            } else if let Some(prefix_only) = &self.prefix_only {
                 prefix_only(&mut ctx, &msg).await;
             }
 
@@ -680,7 +810,16 @@ impl Framework for StandardFramework {
 
         match invoke {
             Invoke::Help(name) => {
-                let args = Args::new(stream.rest(), &self.config.delimiters);
+                if command_prefix.is_some() {
+                    if let Some(normal) = &self.normal_message {
+                        normal(&mut ctx, &msg).await;
+                    }
+
+                    return;
+                }
+
+                let args = Args::new(stream.rest(), &self.config.delimiters)
+                    .with_invocation(prefix.as_deref().unwrap_or_default(), name);
 
                 let owners = self.config.owners.clone();
                 let groups = self.groups.iter().map(|(g, _)| *g).collect::<Vec<_>>();
@@ -701,6 +840,31 @@ impl Framework for StandardFramework {
                 }
             }
             Invoke::Command { command, group } => {
+                // A command's own prefix may only invoke that command.
+                if let Some(p) = command_prefix {
+                    if !command.options.prefixes.contains(&p) {
+                        if let Some(normal) = &self.normal_message {
+                            normal(&mut ctx, &msg).await;
+                        }
+
+                        return;
+                    }
+                }
+
+                // Reaching a command with sub commands means none of them matched the next word.
+                let unknown_sub = match command.options.unknown_sub {
+                    Some(handler) if !command.options.sub_commands.is_empty() => {
+                        let word = stream.peek_until_char(|c| c.is_whitespace());
+
+                        if word.is_empty() {
+                            None
+                        } else {
+                            Some((handler, word.to_string()))
+                        }
+                    },
+                    _ => None,
+                };
+
                 let mut args = {
                     use std::borrow::Cow;
 
@@ -723,7 +887,12 @@ impl Framework for StandardFramework {
                         delims = Cow::Owned(v);
                     }
 
-                    Args::new(stream.rest(), &delims)
+                    Args::with_quotes(
+                        stream.rest(),
+                        &delims,
+                        command.options.delimiter_escape,
+                        command.options.quotes,
+                    )
                 };
 
                 if let Some(error) =
@@ -738,13 +907,43 @@ impl Framework for StandardFramework {
 
                 let name = command.options.names[0].clone();
 
+                if let Some(notice) = command.options.deprecation_notice {
+                    warn!("Deprecated command `{}` was invoked: {}", name, notice);
+                }
+
                 if let Some(before) = &self.before {
                     if !before(&mut ctx, &msg, name).await {
                         return;
                     }
                 }
 
-                let res = (command.fun)(&mut ctx, &msg, args).await;
+                if command.options.delete_invocation {
+                    if let Err(why) = msg.delete(&ctx).await {
+                        debug!("Failed to delete the invocation of `{}`: {:?}", name, why);
+                    }
+                }
+
+                if command.options.typing {
+                    if let Err(why) = msg.channel_id.broadcast_typing(&ctx).await {
+                        debug!("Failed to show typing for `{}`: {:?}", name, why);
+                    }
+                }
+
+                let fut = match unknown_sub {
+                    Some((handler, word)) => (handler.0)(&mut ctx, &msg, &word),
+                    None => (command.fun)(&mut ctx, &msg, args),
+                };
+
+                let res = match command.options.timeout {
+                    Some(secs) => tokio::time::timeout(Duration::from_secs(secs), fut)
+                        .await
+                        .unwrap_or_else(|elapsed| Err(elapsed.into())),
+                    None => fut.await,
+                };
+
+                if let (Err(why), Some(handler)) = (&res, command.options.error_handler) {
+                    (handler.0)(&mut ctx, &msg, why).await;
+                }
 
                 if let Some(after) = &self.after {
                     after(&mut ctx, &msg, name, res).await;
@@ -856,3 +1055,51 @@ pub(crate) fn has_correct_roles(
             .any(|g| member.roles.contains(&g.id))
     }
 }
+
+/// Whether the author of the message is in one of its guild's voice channels.
+/// Voice states are only known through the cache, so without it, or when the
+/// guild is not cached, this is `false`.
+#[allow(unused_variables)]
+async fn in_voice_channel(ctx: &Context, msg: &Message) -> bool {
+    #[cfg(feature = "cache")]
+    {
+        if let Some(guild_id) = msg.guild_id {
+            if let Some(guild) = guild_id.to_guild_cached(&ctx.cache).await {
+                return guild
+                    .voice_states
+                    .get(&msg.author.id)
+                    .map_or(false, |state| state.channel_id.is_some());
+            }
+        }
+    }
+
+    false
+}
+
+/// Whether the author of the message has one of the roles that bypass the
+/// command's bucket. Role names can only be resolved with the cache.
+#[allow(unused_variables)]
+async fn bypasses_cooldown(ctx: &Context, msg: &Message, command: &CommandOptions) -> bool {
+    let roles = match &msg.member {
+        Some(member) => &member.roles,
+        None => return false,
+    };
+
+    if roles.iter().any(|r| command.cooldown_bypass_role_ids.contains(&r.0)) {
+        return true;
+    }
+
+    #[cfg(feature = "cache")]
+    {
+        if let (false, Some(guild_id)) = (command.cooldown_bypass_roles.is_empty(), msg.guild_id) {
+            if let Some(guild_roles) = ctx.cache.guild_roles(guild_id).await {
+                return command.cooldown_bypass_roles
+                    .iter()
+                    .flat_map(|name| guild_roles.values().find(|role| *name == role.name))
+                    .any(|role| roles.contains(&role.id));
+            }
+        }
+    }
+
+    false
+}
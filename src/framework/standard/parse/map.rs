@@ -32,7 +32,9 @@ impl CommandMap {
         for cmd in cmds {
             let sub_map = Arc::new(Self::new(&cmd.options.sub_commands, conf));
 
-            for name in cmd.options.names {
+            let localized = cmd.options.localized_names.iter().map(|(_, name)| name);
+
+            for name in cmd.options.names.iter().chain(localized) {
                 let len = name.chars().count();
                 map.min_length = std::cmp::min(len, map.min_length);
                 map.max_length = std::cmp::max(len, map.max_length);
@@ -120,25 +120,81 @@ pub async fn prefix<'a>(
     prefix
 }
 
+/// Adds the prefixes declared through the `prefixes` option by the commands
+/// of `group`, their sub commands and its sub groups to `prefixes`.
+///
+/// `prefixes` is kept sorted from the longest prefix to the shortest, so that
+/// a prefix is never shadowed by a shorter one it starts with.
+pub fn add_command_prefixes(prefixes: &mut Vec<&'static str>, group: &CommandGroup) {
+    fn in_commands(prefixes: &mut Vec<&'static str>, commands: &[&'static Command]) {
+        for command in commands {
+            prefixes.extend(command.options.prefixes);
+            in_commands(prefixes, command.options.sub_commands);
+        }
+    }
+
+    fn in_group(prefixes: &mut Vec<&'static str>, group: &CommandGroup) {
+        in_commands(prefixes, group.options.commands);
+
+        for group in group.options.sub_groups {
+            in_group(prefixes, group);
+        }
+    }
+
+    in_group(prefixes, group);
+
+    prefixes.sort_unstable_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+    prefixes.dedup();
+}
+
+/// Parse one of the prefixes declared by commands, as collected by
+/// [`add_command_prefixes`]. Only used if no other prefix could be found.
+///
+/// The command that is eventually invoked must itself declare the parsed prefix.
+///
+/// [`add_command_prefixes`]: fn.add_command_prefixes.html
+pub fn command_prefix(stream: &mut Stream<'_>, prefixes: &[&'static str]) -> Option<&'static str> {
+    let prefix = prefixes.iter().copied().find(|prefix| stream.rest().starts_with(prefix))?;
+
+    stream.increment(prefix.len());
+
+    Some(prefix)
+}
+
+#[inline]
+fn check_only_in(
+    msg: &Message,
+    config: &Configuration,
+    only_in: OnlyIn,
+) -> Result<(), DispatchError> {
+    if only_in == OnlyIn::Dm && !msg.is_private() {
+        return Err(DispatchError::OnlyForDM);
+    }
+
+    if (!config.allow_dm || only_in == OnlyIn::Guild) && msg.is_private() {
+        return Err(DispatchError::OnlyForGuilds);
+    }
+
+    Ok(())
+}
+
 /// Checked per valid group or command in the message.
+///
+/// `only_in` is the restriction resolved along the groups and commands parsed so far.
+/// A restriction overridden further down is left for the invoked command to check.
 async fn check_discrepancy(
     #[allow(unused_variables)]
     ctx: &Context,
     msg: &Message,
     config: &Configuration,
     options: &impl CommonOptions,
+    only_in: OnlyIn,
 ) -> Result<(), DispatchError> {
     if options.owners_only() && !config.owners.contains(&msg.author.id) {
         return Err(DispatchError::OnlyForOwners);
     }
 
-    if options.only_in() == OnlyIn::Dm && !msg.is_private() {
-        return Err(DispatchError::OnlyForDM);
-    }
-
-    if (!config.allow_dm || options.only_in() == OnlyIn::Guild) && msg.is_private() {
-        return Err(DispatchError::OnlyForGuilds);
-    }
+    check_only_in(msg, config, only_in)?;
 
     #[cfg(feature = "cache")]
     {
@@ -205,6 +261,7 @@ fn parse_cmd<'a>(
     msg: &'a Message,
     config: &'a Configuration,
     map: &'a CommandMap,
+    only_in: OnlyIn,
 ) -> BoxFuture<'a, Result<&'static Command, ParseError>> {
     async move {
         let (n, r) = try_parse(stream, map, config.by_space, |s| {
@@ -222,14 +279,21 @@ fn parse_cmd<'a>(
                 stream.take_while_char(|c| c.is_whitespace());
             }
 
-            check_discrepancy(ctx, msg, config, &cmd.options).await?;
+            let only_in = cmd.options.only_in.resolve(only_in);
+            let restriction = only_in.unless_overridden(cmd.options.sub_commands, &[]);
+
+            check_discrepancy(ctx, msg, config, &cmd.options, restriction).await?;
 
             if map.is_empty() {
                 return Ok(cmd);
             }
 
-            return match parse_cmd(stream, ctx, msg, config, &map).await {
-                Err(ParseError::UnrecognisedCommand(Some(_))) => Ok(cmd),
+            return match parse_cmd(stream, ctx, msg, config, &map, only_in).await {
+                Err(ParseError::UnrecognisedCommand(Some(_))) => {
+                    check_only_in(msg, config, only_in)?;
+
+                    Ok(cmd)
+                },
                 res => res,
             };
         }
@@ -244,7 +308,8 @@ fn parse_group<'a>(
     msg: &'a Message,
     config: &'a Configuration,
     map: &'a GroupMap,
-) -> BoxFuture<'a, Result<(&'static CommandGroup, Arc<CommandMap>), ParseError>> {
+    only_in: OnlyIn,
+) -> BoxFuture<'a, Result<(&'static CommandGroup, Arc<CommandMap>, OnlyIn), ParseError>> {
     async move {
         let (n, o) = try_parse(stream, map, config.by_space, ToString::to_string);
 
@@ -255,14 +320,18 @@ fn parse_group<'a>(
                 stream.take_while_char(|c| c.is_whitespace());
             }
 
-            check_discrepancy(ctx, msg, config, &group.options).await?;
+            let only_in = group.options.only_in.resolve(only_in);
+            let restriction =
+                only_in.unless_overridden(group.options.commands, group.options.sub_groups);
+
+            check_discrepancy(ctx, msg, config, &group.options, restriction).await?;
 
             if map.is_empty() {
-                return Ok((group, commands));
+                return Ok((group, commands, only_in));
             }
 
-            return match parse_group(stream, ctx, msg, config, &map).await {
-                Err(ParseError::UnrecognisedCommand(None)) => Ok((group, commands)),
+            return match parse_group(stream, ctx, msg, config, &map, only_in).await {
+                Err(ParseError::UnrecognisedCommand(None)) => Ok((group, commands, only_in)),
                 res => res,
             };
         }
@@ -279,11 +348,16 @@ async fn handle_command<'a>(
     config: &'a Configuration,
     map: &'a CommandMap,
     group: &'static CommandGroup,
+    only_in: OnlyIn,
 ) -> Result<Invoke, ParseError> {
-    match parse_cmd(stream, ctx, msg, config, map).await {
+    match parse_cmd(stream, ctx, msg, config, map, only_in).await {
         Ok(command) => Ok(Invoke::Command { group, command }),
         Err(err) => match group.options.default_command {
-            Some(command) => Ok(Invoke::Command { group, command }),
+            Some(command) => {
+                check_only_in(msg, config, command.options.only_in.resolve(only_in))?;
+
+                Ok(Invoke::Command { group, command })
+            },
             None => Err(err),
         },
     }
@@ -296,9 +370,12 @@ async fn handle_group<'a>(
     msg: &'a Message,
     config: &'a Configuration,
     map: &'a GroupMap,
+    only_in: OnlyIn,
 ) -> Result<Invoke, ParseError> {
-    match parse_group(stream, ctx, msg, config, map).await {
-        Ok((group, map)) => handle_command(stream, ctx, msg, config, &map, group).await,
+    match parse_group(stream, ctx, msg, config, map, only_in).await {
+        Ok((group, map, only_in)) => {
+            handle_command(stream, ctx, msg, config, &map, group, only_in).await
+        },
         Err(error) => Err(error),
     }
 }
@@ -355,7 +432,7 @@ pub async fn command(
         match map {
             // Includes [group] itself.
             Map::WithPrefixes(map) => {
-                let res = handle_group(stream, ctx, msg, config, map).await;
+                let res = handle_group(stream, ctx, msg, config, map, OnlyIn::None).await;
 
                 if res.is_ok() {
                     return res;
@@ -368,18 +445,22 @@ pub async fn command(
             Map::Prefixless(subgroups, commands) => {
                 is_prefixless = true;
 
-                let res = handle_group(stream, ctx, msg, config, subgroups).await;
+                let only_in = group.options.only_in;
+                let restriction =
+                only_in.unless_overridden(group.options.commands, group.options.sub_groups);
+
+                let res = handle_group(stream, ctx, msg, config, subgroups, only_in).await;
 
                 if res.is_ok() {
-                    check_discrepancy(ctx, msg, config, &group.options).await?;
+                    check_discrepancy(ctx, msg, config, &group.options, restriction).await?;
 
                     return res;
                 }
 
-                let res = handle_command(stream, ctx, msg, config, commands, group).await;
+                let res = handle_command(stream, ctx, msg, config, commands, group, only_in).await;
 
                 if res.is_ok() {
-                    check_discrepancy(ctx, msg, config, &group.options).await?;
+                    check_discrepancy(ctx, msg, config, &group.options, restriction).await?;
 
                     return res;
                 }
@@ -22,11 +22,53 @@ pub use self::check::*;
 pub enum OnlyIn {
     Dm,
     Guild,
+    /// No restriction of its own. A command or group with this restriction
+    /// inherits the one of its parent command or enclosing group.
     None,
+    /// Usable in both DMs and guilds. This overrides an inherited restriction.
+    Both,
     #[doc(hidden)]
     __Nonexhaustive,
 }
 
+impl OnlyIn {
+    /// Resolves a command's or group's restriction against the one it inherits
+    /// from its parent commands and enclosing groups. Its own restriction takes
+    /// precedence; if it has none, the inherited one is used.
+    pub fn resolve(self, inherited: OnlyIn) -> OnlyIn {
+        match self {
+            OnlyIn::None => inherited,
+            restriction => restriction,
+        }
+    }
+
+    /// The part of a resolved restriction that applies before descending into
+    /// `commands` and `groups`. It is none if any of them, or any command or
+    /// group below them, overrides the restriction with one of its own.
+    pub(crate) fn unless_overridden(
+        self,
+        commands: &[&'static Command],
+        groups: &[&'static CommandGroup],
+    ) -> OnlyIn {
+        match self {
+            OnlyIn::Dm | OnlyIn::Guild if self.is_overridden(commands, groups) => OnlyIn::None,
+            restriction => restriction,
+        }
+    }
+
+    fn is_overridden(self, commands: &[&'static Command], groups: &[&'static CommandGroup]) -> bool {
+        let overrides = |only_in: OnlyIn| only_in != OnlyIn::None && only_in != self;
+
+        commands.iter().any(|command| {
+            overrides(command.options.only_in)
+                || self.is_overridden(command.options.sub_commands, &[])
+        }) || groups.iter().any(|group| {
+            overrides(group.options.only_in)
+                || self.is_overridden(group.options.commands, group.options.sub_groups)
+        })
+    }
+}
+
 impl Default for OnlyIn {
     fn default() -> Self { Self::None }
 }
@@ -36,42 +78,197 @@ pub struct CommandOptions {
     /// A set of checks to be called prior to executing the command. The checks
     /// will short-circuit on the first check that returns `false`.
     pub checks: &'static [&'static Check],
+    /// A set of checks called before the group's and the command's `checks`.
+    pub priority_checks: &'static [&'static Check],
+    /// Whether the checks of the command's group also apply to the command.
+    pub inherit_group_checks: bool,
     /// Ratelimit bucket.
     pub bucket: Option<&'static str>,
+    /// Names of the roles whose members are not limited by the `bucket`.
+    pub cooldown_bypass_roles: &'static [&'static str],
+    /// IDs of the roles whose members are not limited by the `bucket`.
+    pub cooldown_bypass_role_ids: &'static [u64],
     /// Names that the command can be referred to.
     pub names: &'static [&'static str],
+    /// Prefixes that invoke this command on their own, in addition to the
+    /// prefixes of the framework's configuration. If the prefixes of several
+    /// commands start a message, the longest one is used.
+    pub prefixes: &'static [&'static str],
     /// Command description, used by other commands.
     pub desc: Option<&'static str>,
+    /// Category under which the help command lists this command,
+    /// instead of its group. Does not affect dispatch.
+    pub category: Option<&'static str>,
     /// Delimiters used to split the arguments of the command by.
     /// If empty, the [global delimiters](struct.Configuration.html#method.delimiters) are used.
     pub delimiters: &'static [&'static str],
     /// Command usage schema, used by other commands.
     pub usage: Option<&'static str>,
-    /// Example arguments, used by other commands.
-    pub examples: &'static [&'static str],
+    /// The command's usage, split into its literal words and its required
+    /// (`<arg>`) and optional (`[arg]`) arguments.
+    pub usage_parts: &'static [UsageToken],
+    /// Translations of the command's name, each paired with its locale.
+    /// The command can be invoked by any of them.
+    pub localized_names: &'static [(&'static str, &'static str)],
+    /// Translations of the command's usage, each paired with its locale.
+    pub localized_usages: &'static [(&'static str, &'static str)],
+    /// Example arguments, used by other commands, each paired with the output
+    /// it is expected to produce. The output is empty if it was not given.
+    pub examples: &'static [(&'static str, &'static str)],
+    /// The permissions of the users each of `examples` is relevant to, in the
+    /// same order. The help leaves out examples whose permissions the viewer
+    /// lacks; examples without an entry, or with empty permissions, are shown
+    /// to everyone.
+    pub example_permissions: &'static [Permissions],
     /// Minimum amount of arguments that should be passed.
     pub min_args: Option<u16>,
+    /// Minimum time, in seconds, the invoking user must have been a member of
+    /// the guild for. Enforced at dispatch: a member missing from the cache is
+    /// fetched over HTTP, and the command is refused if their join date still
+    /// cannot be found. Commands invoked outside of guilds are unaffected.
+    pub min_membership_seconds: Option<u64>,
+    /// How long, in seconds, the command may run before it is cancelled.
+    ///
+    /// Once the timeout elapses, the command's future is dropped at its
+    /// current `.await`. The command then ends with an error wrapping
+    /// [`tokio::time::Elapsed`], which is given to its error handler and the
+    /// `after` hook like any other error.
+    ///
+    /// [`tokio::time::Elapsed`]: https://docs.rs/tokio/0.2/tokio/time/struct.Elapsed.html
+    pub timeout: Option<u64>,
     /// Maximum amount of arguments that can be passed.
     pub max_args: Option<u16>,
+    /// Minimum amount of arguments when this command is invoked rather than
+    /// one of its sub commands. Takes precedence over `min_args` for commands
+    /// with sub commands.
+    pub base_min_args: Option<u16>,
+    /// IDs of the guilds the command is restricted to. If empty, the command
+    /// may be used in any guild.
+    pub allowed_guilds: &'static [u64],
+    /// IDs of the users the command is restricted to. If empty, anyone may
+    /// use the command.
+    pub allowed_users: &'static [u64],
+    /// Whether the command is declared to be usable as a slash command. Its
+    /// names are then guaranteed to be valid slash command names.
+    pub slash_compatible: bool,
     /// Roles allowed to use this command.
     pub allowed_roles: &'static [&'static str],
     /// Permissions required to use this command.
     pub required_permissions: Permissions,
     /// Whether the command should be displayed in help list or not, used by other commands.
     pub help_available: bool,
+    /// Why the command is hidden from the help, if a reason was given.
+    /// It is meant for privileged users, such as moderators, who may still
+    /// invoke the command.
+    pub help_unavailable_reason: Option<&'static str>,
     /// Whether the command can only be used in dms or guilds; or both.
     pub only_in: OnlyIn,
+    /// Whether the framework deletes the message that invoked the command
+    /// before running it. Requires the bot to be allowed to delete the
+    /// message; otherwise, the command runs with its message kept.
+    pub delete_invocation: bool,
+    /// Whether the framework shows the bot as typing in the invoking channel
+    /// before running the command.
+    pub typing: bool,
+    /// Whether the invoking user must be in one of the guild's voice channels.
+    /// Enforced at dispatch, using the cache. Voice states cannot be read
+    /// otherwise, so the command is refused outside of guilds, when the guild
+    /// is not cached, or when the `cache` feature is disabled.
+    pub requires_voice: bool,
+    /// Whether the command may only be used in age-restricted guilds.
+    /// Not enforced by the framework, as guilds do not expose whether they
+    /// are age-restricted; checks may read it to enforce it themselves.
+    pub requires_age_gated_guild: bool,
     /// Whether the command can only be used by owners or not.
     pub owners_only: bool,
     /// Whether the command treats owners as normal users.
     pub owner_privilege: bool,
     /// Other commands belonging to this command.
     pub sub_commands: &'static [&'static Command],
+    /// Function called if the command returns an error.
+    pub error_handler: Option<CommandErrorHandler>,
+    /// Function called in place of the command if it is given arguments
+    /// that do not start with one of its sub commands.
+    pub unknown_sub: Option<UnknownSubHandler>,
+    /// Exact amount of the command's delimiters that must separate its arguments.
+    /// Delimiters inside quoted arguments or escaped with `delimiter_escape` are not counted.
+    pub num_delimiters: Option<u16>,
+    /// Character that makes the following character literal in an unquoted
+    /// argument, so that arguments may contain delimiters.
+    pub delimiter_escape: Option<char>,
+    /// Characters that surround a quoted argument, `"` unless the command
+    /// sets others. A quoted argument ends at the next occurrence of the
+    /// character it starts with.
+    pub quotes: &'static [char],
+    /// If set, the command is deprecated. It can still be used, but the help
+    /// displays this notice and invocations are logged as warnings.
+    pub deprecation_notice: Option<&'static str>,
+    /// Labels under which the command is reported to metrics, if any.
+    pub metrics: Option<CommandMetrics>,
+    /// The crate feature the command is compiled with, if it is gated on one.
+    pub required_feature: Option<&'static str>,
+}
+
+/// Labels of a command for a metrics sink, set by the `#[metrics]` option.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CommandMetrics {
+    /// The name the command's measurements are recorded under.
+    pub name: &'static str,
+    /// Additional tags attached to the command's measurements.
+    pub tags: &'static [&'static str],
+}
+
+/// A part of a command's usage, as split by the `#[command]` macro.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UsageToken {
+    /// Text outside of any brackets, such as a sub command's name.
+    Literal(&'static str),
+    /// An argument in angle brackets, `<arg>`.
+    Required(&'static str),
+    /// An argument in square brackets, `[arg]`.
+    Optional(&'static str),
 }
 
 pub type CommandError = Box<dyn StdError + Send + Sync>;
 pub type CommandResult<T = ()> = std::result::Result<T, CommandError>;
 pub type CommandFn = for<'fut> fn(&'fut Context, &'fut Message, Args) -> BoxFuture<'fut, CommandResult>;
+pub type CommandErrorFn = for<'fut> fn(&'fut Context, &'fut Message, &'fut CommandError) -> BoxFuture<'fut, ()>;
+
+/// A command-specific error handler, set by the `#[on_error]` option.
+#[derive(Clone, Copy)]
+pub struct CommandErrorHandler(pub CommandErrorFn);
+
+impl fmt::Debug for CommandErrorHandler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("<fn>")
+    }
+}
+
+impl PartialEq for CommandErrorHandler {
+    #[inline]
+    fn eq(&self, other: &CommandErrorHandler) -> bool {
+        self.0 as usize == other.0 as usize
+    }
+}
+
+pub type UnknownSubFn = for<'fut> fn(&'fut Context, &'fut Message, &'fut str) -> BoxFuture<'fut, CommandResult>;
+
+/// A handler for unknown sub commands, set by the `#[unknown_sub]` option.
+#[derive(Clone, Copy)]
+pub struct UnknownSubHandler(pub UnknownSubFn);
+
+impl fmt::Debug for UnknownSubHandler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("<fn>")
+    }
+}
+
+impl PartialEq for UnknownSubHandler {
+    #[inline]
+    fn eq(&self, other: &UnknownSubHandler) -> bool {
+        self.0 as usize == other.0 as usize
+    }
+}
 
 pub struct Command {
     pub fun: CommandFn,
@@ -93,6 +290,40 @@ impl PartialEq for Command {
     }
 }
 
+/// Information about a command, available through its type.
+///
+/// Implemented by the `FooCommandMarker` types generated with
+/// `#[command(introspect)]`, so that commands can be inspected generically.
+pub trait CommandInfo {
+    /// The command's static.
+    fn command() -> &'static Command;
+
+    /// The command's primary name.
+    fn name() -> &'static str {
+        Self::command().options.names[0]
+    }
+
+    /// The command's other names.
+    fn aliases() -> &'static [&'static str] {
+        &Self::command().options.names[1..]
+    }
+
+    /// The command's description.
+    fn description() -> Option<&'static str> {
+        Self::command().options.desc
+    }
+
+    /// Minimum amount of arguments the command takes.
+    fn min_args() -> Option<u16> {
+        Self::command().options.min_args
+    }
+
+    /// Maximum amount of arguments the command takes.
+    fn max_args() -> Option<u16> {
+        Self::command().options.max_args
+    }
+}
+
 pub type HelpCommandFn = for<'fut> fn(
     &'fut Context,
     &'fut Message,
@@ -134,12 +365,29 @@ pub enum HelpBehaviour {
     Nothing,
     /// Strikes a command by applying `~~{command_name}~~`.
     Strike,
+    /// Does not list a command in the help-menu, but counts it in a single
+    /// line of hidden commands per group.
+    Collapse,
     /// Does not list a command in the help-menu.
     Hide,
     #[doc(hidden)]
     __Nonexhaustive,
 }
 
+/// How the help orders the groups it lists.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum GroupOrder {
+    /// Groups are listed in the order they were passed to the help.
+    Declaration,
+    /// Groups are listed by their `order`, lowest first. Groups of the same
+    /// weight keep their declaration order.
+    Weight,
+    /// Groups are listed alphabetically by their name.
+    Name,
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct HelpOptions {
     /// Which names should the help command use for dispatching.
@@ -165,6 +413,8 @@ pub struct HelpOptions {
     pub guild_only_text: &'static str,
     /// Text labelling a command's names of checks.
     pub checks_label: &'static str,
+    /// Text labelling a deprecated command's notice.
+    pub deprecated_label: &'static str,
     /// Text labelling a command's subcommands
     pub sub_commands_label: &'static str,
     /// Text specifying that a command is only usable in via DM.
@@ -173,11 +423,22 @@ pub struct HelpOptions {
     pub dm_and_guild_text: &'static str,
     /// Text expressing that a command is available.
     pub available_text: &'static str,
+    /// Whether to show in which channels a command is available.
+    /// If `false`, the `guild_only_text`, `dm_only_text` and `dm_and_guild_text`
+    /// lines are left out.
+    pub show_availability: bool,
+    /// Whether to show the permissions a command requires.
+    pub show_permissions: bool,
+    /// Text labelling a command's required permissions.
+    pub permissions_label: &'static str,
     /// Error-message once a command could not be found.
     /// Output-example (without whitespace between both substitutions: `{command_not_found_text}{command_name}`
     /// `{command_name}` describes user's input as in: `{prefix}help {command_name}`.
     pub command_not_found_text: &'static str,
     /// Explains the user on how to use access a single command's details.
+    ///
+    /// The `{prefix}` and `{command}` tokens are replaced with the prefix and
+    /// name the help command was invoked with.
     pub individual_command_tip: &'static str,
     /// Explains reasoning behind strikethrough-commands, see fields requiring `HelpBehaviour` for further information.
     /// If `HelpBehaviour::Strike` is unused, this field will evaluate to `None` during creation
@@ -213,6 +474,33 @@ pub struct HelpOptions {
     /// Help will use this as prefix to express how deeply nested a command or
     /// group is.
     pub indention_prefix: &'static str,
+    /// How many groups the embedded help lists per message. Further groups
+    /// are sent in additional messages.
+    pub max_embed_fields: usize,
+    /// How the groups are ordered in the listing of all commands.
+    pub sort_groups_by: GroupOrder,
+    /// Maximum length of the list of a group's commands in the embedded help.
+    /// Longer lists are cut off.
+    pub max_field_length: usize,
+    /// After how many characters the plain help is split into several
+    /// messages. If 0, the help is sent as a single message.
+    pub paginate_after: usize,
+    /// Text shown at the bottom of the command listing and of a single
+    /// command's details. If empty, no footer is shown.
+    ///
+    /// Supports the same tokens as `individual_command_tip`.
+    pub footer_text: &'static str,
+    /// Text put in front of the invocations shown in usage and example
+    /// lines, such as `"@Bot "` for bots invoked by mention.
+    ///
+    /// Supports the same tokens as `individual_command_tip`.
+    pub prefix_display: &'static str,
+    /// The singular and plural forms of the line counting a group's collapsed
+    /// commands. `{count}` is replaced by the number of collapsed commands.
+    pub collapsed_commands_text: (&'static str, &'static str),
+    /// The locale the help is shown in. Commands with a usage for this
+    /// locale in `localized_usages` show it instead of their `usage`.
+    pub locale: &'static str,
 }
 
 #[derive(Debug, Default, PartialEq)]
@@ -226,7 +514,22 @@ pub struct GroupOptions {
     pub required_permissions: Permissions,
     pub checks: &'static [&'static Check],
     pub default_command: Option<&'static Command>,
+    /// Minimum amount of arguments for commands of this group that don't set
+    /// their own `min_args`.
+    pub default_min_args: Option<u16>,
+    /// Maximum amount of arguments for commands of this group that don't set
+    /// their own `max_args`.
+    pub default_max_args: Option<u16>,
+    /// Ratelimit bucket shared by the commands of this group that don't set
+    /// their own `bucket`.
+    pub bucket: Option<&'static str>,
+    /// Description shown when the help is asked about this group.
     pub description: Option<&'static str>,
+    /// Short description shown next to this group in the help's listing.
+    pub summary: Option<&'static str>,
+    /// Weight of this group, used to order the help's listing if it sorts
+    /// groups by weight. Lower weights are listed first.
+    pub order: i32,
     pub commands: &'static [&'static Command],
     pub sub_groups: &'static [&'static CommandGroup],
 }
@@ -267,3 +570,107 @@ mod levenshtein_tests {
         assert_eq!(HelpBehaviour::Hide, std::cmp::max(HelpBehaviour::Nothing, HelpBehaviour::Hide));
     }
 }
+
+#[cfg(test)]
+mod only_in_tests {
+    use super::{Args, Command, CommandGroup, CommandOptions, CommandResult, GroupOptions, OnlyIn};
+    use crate::client::Context;
+    use crate::model::channel::Message;
+    use futures::future::BoxFuture;
+
+    fn run<'fut>(_: &'fut Context, _: &'fut Message, _: Args) -> BoxFuture<'fut, CommandResult> {
+        Box::pin(async { Ok(()) })
+    }
+
+    fn command(only_in: OnlyIn, sub_commands: Vec<&'static Command>) -> &'static Command {
+        let options = Box::leak(Box::new(CommandOptions {
+            only_in,
+            sub_commands: Box::leak(sub_commands.into_boxed_slice()),
+            ..CommandOptions::default()
+        }));
+
+        Box::leak(Box::new(Command { fun: run, options }))
+    }
+
+    fn group(
+        only_in: OnlyIn,
+        commands: Vec<&'static Command>,
+        sub_groups: Vec<&'static CommandGroup>,
+    ) -> &'static CommandGroup {
+        let options = Box::leak(Box::new(GroupOptions {
+            only_in,
+            commands: Box::leak(commands.into_boxed_slice()),
+            sub_groups: Box::leak(sub_groups.into_boxed_slice()),
+            ..GroupOptions::default()
+        }));
+
+        Box::leak(Box::new(CommandGroup { name: "group", options }))
+    }
+
+    #[test]
+    fn inherit() {
+        assert_eq!(OnlyIn::default(), OnlyIn::None);
+        assert_eq!(OnlyIn::None.resolve(OnlyIn::Guild), OnlyIn::Guild);
+        assert_eq!(OnlyIn::None.resolve(OnlyIn::Dm), OnlyIn::Dm);
+        assert_eq!(OnlyIn::None.resolve(OnlyIn::None), OnlyIn::None);
+
+        let inheriting = command(OnlyIn::None, vec![command(OnlyIn::None, vec![])]);
+        let commands = [inheriting];
+        let groups = [group(OnlyIn::None, vec![inheriting], vec![])];
+
+        assert_eq!(OnlyIn::Guild.unless_overridden(&commands, &groups), OnlyIn::Guild);
+        assert_eq!(OnlyIn::Dm.unless_overridden(&commands, &groups), OnlyIn::Dm);
+    }
+
+    #[test]
+    fn guilds() {
+        assert_eq!(OnlyIn::Guild.resolve(OnlyIn::None), OnlyIn::Guild);
+        assert_eq!(OnlyIn::Guild.resolve(OnlyIn::Dm), OnlyIn::Guild);
+        assert_eq!(OnlyIn::Guild.resolve(OnlyIn::Both), OnlyIn::Guild);
+
+        let commands = [command(OnlyIn::Guild, vec![])];
+
+        assert_eq!(OnlyIn::Guild.unless_overridden(&commands, &[]), OnlyIn::Guild);
+        assert_eq!(OnlyIn::Dm.unless_overridden(&commands, &[]), OnlyIn::None);
+    }
+
+    #[test]
+    fn dms() {
+        assert_eq!(OnlyIn::Dm.resolve(OnlyIn::None), OnlyIn::Dm);
+        assert_eq!(OnlyIn::Dm.resolve(OnlyIn::Guild), OnlyIn::Dm);
+        assert_eq!(OnlyIn::Dm.resolve(OnlyIn::Both), OnlyIn::Dm);
+
+        let commands = [command(OnlyIn::Dm, vec![])];
+
+        assert_eq!(OnlyIn::Dm.unless_overridden(&commands, &[]), OnlyIn::Dm);
+        assert_eq!(OnlyIn::Guild.unless_overridden(&commands, &[]), OnlyIn::None);
+    }
+
+    #[test]
+    fn both() {
+        assert_eq!(OnlyIn::Both.resolve(OnlyIn::None), OnlyIn::Both);
+        assert_eq!(OnlyIn::Both.resolve(OnlyIn::Guild), OnlyIn::Both);
+        assert_eq!(OnlyIn::Both.resolve(OnlyIn::Dm), OnlyIn::Both);
+
+        let commands = [command(OnlyIn::None, vec![]), command(OnlyIn::Both, vec![])];
+
+        assert_eq!(OnlyIn::Guild.unless_overridden(&commands, &[]), OnlyIn::None);
+        assert_eq!(OnlyIn::Dm.unless_overridden(&commands, &[]), OnlyIn::None);
+        assert_eq!(OnlyIn::Both.unless_overridden(&commands, &[]), OnlyIn::Both);
+    }
+
+    #[test]
+    fn overridden_along_the_chain() {
+        let sub_command = command(OnlyIn::Both, vec![]);
+        let parent = command(OnlyIn::None, vec![sub_command]);
+        let sub_group = group(OnlyIn::None, vec![parent], vec![]);
+        let groups = [group(OnlyIn::Guild, vec![], vec![sub_group])];
+
+        assert_eq!(OnlyIn::Guild.unless_overridden(&[parent], &[]), OnlyIn::None);
+        assert_eq!(OnlyIn::Guild.unless_overridden(&[], &groups), OnlyIn::None);
+
+        let groups = [group(OnlyIn::Dm, vec![], vec![])];
+
+        assert_eq!(OnlyIn::Guild.unless_overridden(&[], &groups), OnlyIn::None);
+    }
+}
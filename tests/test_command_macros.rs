@@ -0,0 +1,222 @@
+#![cfg(feature = "standard_framework")]
+
+use std::collections::HashSet;
+
+use serenity::client::Context;
+use serenity::framework::standard::{
+    macros::{assert_command_options, command, help},
+    Args, Command, CommandGroup, CommandInfo, CommandOptions, CommandResult, HelpOptions, OnlyIn,
+};
+use serenity::model::{channel::Message, id::UserId, permissions::Permissions};
+
+#[command(as_const)]
+#[aliases("p")]
+async fn ping(_ctx: &Context, _msg: &Message) -> CommandResult {
+    Ok(())
+}
+
+const PING: &Command = &PING_COMMAND;
+
+assert_command_options!(PING_COMMAND_OPTIONS, usage = None, only_in = OnlyIn::None);
+
+#[test]
+fn as_const_command() {
+    assert_eq!(PING.options.names, ["ping", "p"]);
+    assert_eq!(PING_COMMAND_OPTIONS.names, PING.options.names);
+}
+
+const fn ping_options() -> CommandOptions {
+    PING_COMMAND_OPTIONS
+}
+
+#[command(options_from = ping_options)]
+async fn pong(_ctx: &Context, _msg: &Message) -> CommandResult {
+    Ok(())
+}
+
+#[test]
+fn options_from_builder() {
+    assert_eq!(PONG_COMMAND_OPTIONS, PING_COMMAND_OPTIONS);
+    assert_eq!(PONG_COMMAND.options.names, ["ping", "p"]);
+    assert_eq!(pong_command_names(), ["ping", "p"]);
+}
+
+#[command(inline_options)]
+#[aliases("i")]
+async fn inline(_ctx: &Context, _msg: &Message) -> CommandResult {
+    Ok(())
+}
+
+// Would clash with the options static, were one generated.
+#[allow(dead_code)]
+const INLINE_COMMAND_OPTIONS: () = ();
+
+#[test]
+fn inline_options() {
+    assert_eq!(INLINE_COMMAND.options.names, ["inline", "i"]);
+    assert_eq!(inline_command_names(), ["inline", "i"]);
+}
+
+#[command]
+#[usage("<user>")]
+#[localized_usage(de = "<nutzer>", fr = "<utilisateur>")]
+async fn kick(_ctx: &Context, _msg: &Message) -> CommandResult {
+    Ok(())
+}
+
+#[test]
+fn localized_usage() {
+    assert_eq!(KICK_COMMAND.options.usage, Some("<user>"));
+    assert_eq!(KICK_COMMAND.options.localized_usages, [("de", "<nutzer>"), ("fr", "<utilisateur>")]);
+}
+
+#[command(introspect)]
+#[aliases("b", "banish")]
+#[description("Bans a user.")]
+#[min_args(1)]
+#[max_args(2)]
+async fn ban(_ctx: &Context, _msg: &Message) -> CommandResult {
+    Ok(())
+}
+
+#[test]
+fn introspect() {
+    assert!(std::ptr::eq(BanCommandMarker::command(), &BAN_COMMAND));
+    assert_eq!(BanCommandMarker::name(), "ban");
+    assert_eq!(BanCommandMarker::aliases(), ["b", "banish"]);
+    assert_eq!(BanCommandMarker::description(), Some("Bans a user."));
+    assert_eq!(BanCommandMarker::min_args(), Some(1));
+    assert_eq!(BanCommandMarker::max_args(), Some(2));
+}
+
+#[test]
+fn option_assertions() {
+    assert_command_options!(
+        BAN_COMMAND_OPTIONS,
+        desc = "Bans a user.",
+        min_args = 1,
+        max_args = Some(2)
+    );
+}
+
+#[command(name_matcher)]
+#[aliases("m", "silence")]
+async fn mute(_ctx: &Context, _msg: &Message) -> CommandResult {
+    Ok(())
+}
+
+#[test]
+fn name_matcher() {
+    assert!(mute_matches("mute"));
+    assert!(mute_matches("silence"));
+    assert!(!mute_matches("Mute"));
+    assert!(!mute_matches("ban"));
+}
+
+#[command]
+#[requires_voice]
+async fn play(_ctx: &Context, _msg: &Message) -> CommandResult {
+    Ok(())
+}
+
+#[command]
+#[requires_voice(false)]
+async fn queue(_ctx: &Context, _msg: &Message) -> CommandResult {
+    Ok(())
+}
+
+#[test]
+fn requires_voice() {
+    assert!(PLAY_COMMAND.options.requires_voice);
+    assert!(!QUEUE_COMMAND.options.requires_voice);
+    assert!(!PING_COMMAND.options.requires_voice);
+}
+
+#[help]
+#[collapsed_commands_text("*{count} command hidden*", "*{count} commands hidden*")]
+#[show_availability(false)]
+async fn my_help(
+    _ctx: &Context,
+    _msg: &Message,
+    _args: Args,
+    _help_options: &'static HelpOptions,
+    _groups: &[&'static CommandGroup],
+    _owners: HashSet<UserId>,
+) -> CommandResult {
+    Ok(())
+}
+
+#[test]
+fn collapsed_commands_text() {
+    assert_eq!(
+        MY_HELP.options.collapsed_commands_text,
+        ("*{count} command hidden*", "*{count} commands hidden*")
+    );
+}
+
+#[command]
+#[example("10", requires = MANAGE_MESSAGES)]
+#[example(input = "all", output = "Purged.", requires = MANAGE_MESSAGES | MANAGE_CHANNELS)]
+#[example("1")]
+async fn purge(_ctx: &Context, _msg: &Message) -> CommandResult {
+    Ok(())
+}
+
+#[test]
+fn example_permissions() {
+    let options = PURGE_COMMAND.options;
+
+    assert_eq!(options.examples, [("10", ""), ("all", "Purged."), ("1", "")]);
+    assert_eq!(
+        options.example_permissions,
+        [
+            Permissions::MANAGE_MESSAGES,
+            Permissions::MANAGE_MESSAGES | Permissions::MANAGE_CHANNELS,
+            Permissions::empty(),
+        ]
+    );
+}
+
+#[command]
+#[requires_age_gated_guild]
+#[typing]
+#[delete_invocation]
+#[inherit_group_checks(false)]
+async fn confess(_ctx: &Context, _msg: &Message) -> CommandResult {
+    Ok(())
+}
+
+#[test]
+fn flag_options() {
+    let options = CONFESS_COMMAND.options;
+
+    assert!(options.requires_age_gated_guild);
+    assert!(options.typing);
+    assert!(options.delete_invocation);
+    assert!(!options.inherit_group_checks);
+
+    let defaults = PING_COMMAND.options;
+
+    assert!(!defaults.requires_age_gated_guild);
+    assert!(!defaults.typing);
+    assert!(!defaults.delete_invocation);
+    assert!(defaults.inherit_group_checks);
+}
+
+#[help]
+async fn plain_help(
+    _ctx: &Context,
+    _msg: &Message,
+    _args: Args,
+    _help_options: &'static HelpOptions,
+    _groups: &[&'static CommandGroup],
+    _owners: HashSet<UserId>,
+) -> CommandResult {
+    Ok(())
+}
+
+#[test]
+fn show_availability() {
+    assert!(!MY_HELP.options.show_availability);
+    assert!(PLAIN_HELP.options.show_availability);
+}